@@ -0,0 +1,752 @@
+//! Generates Rust types for `bevy_tiled_toolkit` from custom classes and enums
+//! of a `.tiled-project` file.
+//!
+//! Classes used by tiles, objects or maps become components with
+//! `#[derive(TiledComponent)]`, other classes become `#[derive(TiledClass)]`
+//! structs, and enums implement `FromTiledProperty`. Defaults of members are
+//! taken from the project. Call [build] from `build.rs`:
+//! ```no_run
+//! // build.rs, inside `fn main`
+//! bevy_tiled_toolkit_codegen::build(
+//!     "assets/tiled/game.tiled-project",
+//!     "tiled_types.rs",
+//! )
+//! .unwrap();
+//! ```
+//! and include the generated file into a separate module:
+//! ```ignore
+//! mod tiled_types {
+//!     include!(concat!(env!("OUT_DIR"), "/tiled_types.rs"));
+//! }
+//!
+//! app.add_plugins(TiledToolkitPlugin::default());
+//! tiled_types::register_tiled_types(&mut app);
+//! ```
+//! The same code can be generated once with the binary:
+//! `bevy_tiled_toolkit_codegen game.tiled-project src/tiled_types.rs`.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Errors which can be returned when generating types.
+#[derive(Debug)]
+pub enum CodegenError {
+    /// Project can't be read or generated code can't be written.
+    Io(std::io::Error),
+    /// Project is not a valid JSON.
+    Json(serde_json::Error),
+    /// `OUT_DIR` is not set, `build` should be called from a build script.
+    NoOutDir,
+    /// Two Tiled names of types, fields or enum values become the same Rust
+    /// identifier.
+    NameCollision {
+        first: String,
+        second: String,
+        ident: String,
+    },
+}
+
+impl Display for CodegenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodegenError::Io(e) => write!(f, "Can't read or write file: {}", e),
+            CodegenError::Json(e) => write!(f, "Can't parse project: {}", e),
+            CodegenError::NoOutDir => {
+                f.write_str("OUT_DIR is not set, call it from build.rs")
+            }
+            CodegenError::NameCollision {
+                first,
+                second,
+                ident,
+            } => write!(
+                f,
+                "{:?} and {:?} both become `{}`, rename one of them",
+                first, second, ident
+            ),
+        }
+    }
+}
+
+impl Error for CodegenError {}
+
+impl From<std::io::Error> for CodegenError {
+    fn from(e: std::io::Error) -> Self {
+        CodegenError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CodegenError {
+    fn from(e: serde_json::Error) -> Self {
+        CodegenError::Json(e)
+    }
+}
+
+/// Reads the project and writes generated types to `OUT_DIR/file_name`,
+/// the build script is rerun when the project changes.
+pub fn build(
+    project_path: impl AsRef<Path>,
+    file_name: &str,
+) -> Result<(), CodegenError> {
+    let project_path = project_path.as_ref();
+    println!("cargo:rerun-if-changed={}", project_path.display());
+    let out_dir = std::env::var_os("OUT_DIR").ok_or(CodegenError::NoOutDir)?;
+    generate_file(project_path, PathBuf::from(out_dir).join(file_name))
+}
+
+/// Reads the project and writes generated types to `out_path`.
+pub fn generate_file(
+    project_path: impl AsRef<Path>,
+    out_path: impl AsRef<Path>,
+) -> Result<(), CodegenError> {
+    let project = std::fs::read_to_string(project_path)?;
+    std::fs::write(out_path, generate(&project)?)?;
+    Ok(())
+}
+
+/// Returns Rust source with types of the project JSON.
+pub fn generate(project_json: &str) -> Result<String, CodegenError> {
+    let project: Project = serde_json::from_str(project_json)?;
+    check_idents(&project)?;
+    Ok(Generator::new(&project).generate())
+}
+
+// ───── Project file format ──────────────────────────────────────────────── //
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Project {
+    #[serde(default)]
+    property_types: Vec<PropertyType>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum PropertyType {
+    #[serde(rename_all = "camelCase")]
+    Class {
+        name: String,
+        #[serde(default)]
+        use_as: Vec<String>,
+        #[serde(default)]
+        members: Vec<Member>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Enum {
+        name: String,
+        #[serde(default)]
+        values: Vec<String>,
+        #[serde(default)]
+        values_as_flags: bool,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Member {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    property_type: Option<String>,
+    #[serde(default)]
+    value: Value,
+}
+
+// ───── Generator ────────────────────────────────────────────────────────── //
+
+struct Generator<'a> {
+    project: &'a Project,
+    types: HashMap<&'a str, &'a PropertyType>,
+    out: String,
+}
+
+impl<'a> Generator<'a> {
+    fn new(project: &'a Project) -> Self {
+        let types = project
+            .property_types
+            .iter()
+            .map(|property_type| match property_type {
+                PropertyType::Class { name, .. }
+                | PropertyType::Enum { name, .. } => {
+                    (name.as_str(), property_type)
+                }
+            })
+            .collect();
+        Generator {
+            project,
+            types,
+            out: String::new(),
+        }
+    }
+
+    fn generate(mut self) -> String {
+        self.out.push_str(
+            "// Generated by bevy_tiled_toolkit_codegen, do not edit.\n\n\
+             #[allow(unused_imports)]\n\
+             use bevy::prelude::*;\n\
+             #[allow(unused_imports)]\n\
+             use bevy_tiled_toolkit::__private::tiled;\n\
+             #[allow(unused_imports)]\n\
+             use bevy_tiled_toolkit::prelude::*;\n\
+             #[allow(unused_imports)]\n\
+             use bevy_tiled_toolkit::property_value::FromTiledProperty;\n",
+        );
+
+        let mut components = Vec::new();
+        for property_type in self.project.property_types.iter() {
+            match property_type {
+                PropertyType::Class {
+                    name,
+                    use_as,
+                    members,
+                } => {
+                    let component = is_component(use_as);
+                    self.class(name, members, component);
+                    if component {
+                        components.push(type_ident(name));
+                    }
+                }
+                PropertyType::Enum {
+                    name,
+                    values,
+                    values_as_flags,
+                } => self.enumeration(name, values, *values_as_flags),
+            }
+        }
+
+        self.out.push_str(
+            "\n/// Registers all generated components.\n\
+             #[allow(unused_variables)]\n\
+             pub fn register_tiled_types(app: &mut App) {\n",
+        );
+        for component in components {
+            let _ = writeln!(
+                self.out,
+                "    app.register_tiled_component::<{}>();",
+                component
+            );
+        }
+        self.out.push_str("}\n");
+        self.out
+    }
+
+    fn class(&mut self, name: &str, members: &[Member], component: bool) {
+        let ident = type_ident(name);
+        let derive = if component {
+            "Component, TiledComponent"
+        } else {
+            "TiledClass"
+        };
+        let _ = writeln!(self.out, "\n#[derive({}, Clone, Debug)]", derive);
+        // Missing members are taken from `impl Default` with project defaults
+        if ident != name {
+            let _ = writeln!(self.out, "#[tiled(class = {:?}, default)]", name);
+        } else {
+            self.out.push_str("#[tiled(default)]\n");
+        }
+        let _ = writeln!(self.out, "pub struct {} {{", ident);
+        for member in members {
+            let field = field_ident(&member.name);
+            if field != member.name {
+                let _ = writeln!(
+                    self.out,
+                    "    #[tiled(rename = {:?})]",
+                    member.name
+                );
+            }
+            let _ = writeln!(
+                self.out,
+                "    pub {}: {},",
+                field,
+                self.rust_type(member)
+            );
+        }
+        self.out.push_str("}\n");
+
+        let _ = writeln!(
+            self.out,
+            "\nimpl Default for {ident} {{\n    \
+             fn default() -> Self {{\n        \
+             {ident} {{",
+        );
+        for member in members {
+            let _ = writeln!(
+                self.out,
+                "            {}: {},",
+                field_ident(&member.name),
+                self.default_value(member, &member.value)
+            );
+        }
+        self.out.push_str("        }\n    }\n}\n");
+    }
+
+    fn enumeration(&mut self, name: &str, values: &[String], flags: bool) {
+        let ident = type_ident(name);
+        if values.is_empty() {
+            let _ = writeln!(self.out, "\n// Enum {} has no values.", name);
+            return;
+        }
+        let variants: Vec<String> =
+            values.iter().map(|value| type_ident(value)).collect();
+
+        let _ = writeln!(
+            self.out,
+            "\n#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]\n\
+             pub enum {} {{\n    #[default]",
+            ident
+        );
+        for variant in variants.iter() {
+            let _ = writeln!(self.out, "    {},", variant);
+        }
+        self.out.push_str("}\n");
+
+        let _ = writeln!(
+            self.out,
+            "\nimpl {ident} {{\n    \
+             /// Values in the order of the project.\n    \
+             pub const VALUES: [{ident}; {len}] = [{list}];\n\n    \
+             pub fn from_name(name: &str) -> Option<Self> {{\n        \
+             match name {{",
+            len = variants.len(),
+            list = variants
+                .iter()
+                .map(|variant| format!("{}::{}", ident, variant))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+        for (value, variant) in values.iter().zip(variants.iter()) {
+            let _ = writeln!(
+                self.out,
+                "            {:?} => Some({}::{}),",
+                value, ident, variant
+            );
+        }
+        self.out.push_str(
+            "            _ => None,\n        }\n    }\n\n    \
+             pub fn name(&self) -> &'static str {\n        \
+             match self {\n",
+        );
+        for (value, variant) in values.iter().zip(variants.iter()) {
+            let _ = writeln!(
+                self.out,
+                "            {}::{} => {:?},",
+                ident, variant, value
+            );
+        }
+        self.out.push_str("        }\n    }\n}\n");
+
+        let _ = writeln!(
+            self.out,
+            "\nimpl FromTiledProperty for {ident} {{\n    \
+             fn from_tiled_property(\n        \
+             value: &tiled::PropertyValue,\n        \
+             _asset_server: &AssetServer,\n    \
+             ) -> Result<Self, TiledPropertyError> {{\n        \
+             let variant = match value {{\n            \
+             tiled::PropertyValue::StringValue(v) => {ident}::from_name(v),\n            \
+             tiled::PropertyValue::IntValue(v) => usize::try_from(*v)\n                \
+             .ok()\n                \
+             .and_then(|idx| {ident}::VALUES.get(idx).copied()),\n            \
+             _ => None,\n        \
+             }};\n        \
+             variant.ok_or_else(|| TiledPropertyError::mismatch({name:?}, value))\n    \
+             }}\n\
+             }}",
+        );
+
+        if flags {
+            let _ = writeln!(
+                self.out,
+                "\n#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]\n\
+                 pub struct {ident}Flags(pub Vec<{ident}>);\n\n\
+                 impl FromTiledProperty for {ident}Flags {{\n    \
+                 fn from_tiled_property(\n        \
+                 value: &tiled::PropertyValue,\n        \
+                 _asset_server: &AssetServer,\n    \
+                 ) -> Result<Self, TiledPropertyError> {{\n        \
+                 let flags = match value {{\n            \
+                 tiled::PropertyValue::StringValue(v) => v\n                \
+                 .split(',')\n                \
+                 .filter(|flag| !flag.is_empty())\n                \
+                 .map({ident}::from_name)\n                \
+                 .collect(),\n            \
+                 tiled::PropertyValue::IntValue(v) => Some(\n                \
+                 {ident}::VALUES\n                    \
+                 .iter()\n                    \
+                 .enumerate()\n                    \
+                 .filter(|(idx, _)| *idx < 32 && v & (1 << idx) != 0)\n                    \
+                 .map(|(_, value)| *value)\n                    \
+                 .collect(),\n            \
+                 ),\n            \
+                 _ => None,\n        \
+                 }};\n        \
+                 flags\n            \
+                 .map({ident}Flags)\n            \
+                 .ok_or_else(|| TiledPropertyError::mismatch({name:?}, value))\n    \
+                 }}\n\
+                 }}",
+            );
+        }
+    }
+
+    /// Rust type of the class member.
+    fn rust_type(&self, member: &Member) -> String {
+        if let Some(property_type) = member.property_type.as_deref() {
+            match self.types.get(property_type) {
+                Some(PropertyType::Enum {
+                    values_as_flags: true,
+                    ..
+                }) => return format!("{}Flags", type_ident(property_type)),
+                Some(_) => return type_ident(property_type),
+                None => {}
+            }
+        }
+        match member.kind.as_str() {
+            "bool" => "bool",
+            "int" => "i32",
+            "float" => "f32",
+            "color" => "Color",
+            "object" => "u32",
+            _ => "String",
+        }
+        .to_string()
+    }
+
+    /// Rust expression with the default value of the class member.
+    fn default_value(&self, member: &Member, value: &Value) -> String {
+        if value.is_null() {
+            return "Default::default()".to_string();
+        }
+        let property_type = member.property_type.as_deref().unwrap_or("");
+        match self.types.get(property_type) {
+            Some(PropertyType::Enum {
+                values,
+                values_as_flags,
+                ..
+            }) => {
+                let ident = type_ident(property_type);
+                let names: Vec<&str> = match value {
+                    Value::String(v) => {
+                        v.split(',').filter(|v| !v.is_empty()).collect()
+                    }
+                    Value::Number(v) => {
+                        let v = v.as_u64().unwrap_or_default();
+                        values
+                            .iter()
+                            .enumerate()
+                            .filter(|(idx, _)| match values_as_flags {
+                                true => *idx < 64 && v & (1 << idx) != 0,
+                                false => *idx as u64 == v,
+                            })
+                            .map(|(_, value)| value.as_str())
+                            .collect()
+                    }
+                    _ => Vec::new(),
+                };
+                let variants: Vec<String> = names
+                    .iter()
+                    .filter(|name| values.iter().any(|value| value == *name))
+                    .map(|name| format!("{}::{}", ident, type_ident(name)))
+                    .collect();
+                match (values_as_flags, variants.first()) {
+                    (true, _) => {
+                        format!("{}Flags(vec![{}])", ident, variants.join(", "))
+                    }
+                    (false, Some(variant)) => variant.clone(),
+                    (false, None) => "Default::default()".to_string(),
+                }
+            }
+            Some(PropertyType::Class { members, .. }) => {
+                let Value::Object(object) = value else {
+                    return "Default::default()".to_string();
+                };
+                let mut fields = String::new();
+                for (name, value) in object.iter() {
+                    let Some(nested) =
+                        members.iter().find(|nested| nested.name == *name)
+                    else {
+                        continue;
+                    };
+                    let _ = write!(
+                        fields,
+                        "{}: {}, ",
+                        field_ident(name),
+                        self.default_value(nested, value)
+                    );
+                }
+                format!(
+                    "{} {{ {}..Default::default() }}",
+                    type_ident(property_type),
+                    fields
+                )
+            }
+            None => match (member.kind.as_str(), value) {
+                ("bool", Value::Bool(v)) => v.to_string(),
+                ("int" | "object", Value::Number(v)) => v.to_string(),
+                ("float", Value::Number(v)) => {
+                    format!("{:?}", v.as_f64().unwrap_or_default())
+                }
+                ("color", Value::String(v)) => match parse_color(v) {
+                    Some([a, r, g, b]) => {
+                        format!("Color::rgba_u8({}, {}, {}, {})", r, g, b, a)
+                    }
+                    None => "Default::default()".to_string(),
+                },
+                (_, Value::String(v)) => format!("{:?}.to_string()", v),
+                _ => "Default::default()".to_string(),
+            },
+        }
+    }
+}
+
+// ───── Utility functions ────────────────────────────────────────────────── //
+
+/// Classes without `useAs` come from older Tiled versions and can be used
+/// anywhere.
+fn is_component(use_as: &[String]) -> bool {
+    use_as.is_empty()
+        || use_as
+            .iter()
+            .any(|target| matches!(target.as_str(), "object" | "tile" | "map"))
+}
+
+/// Parses `#AARRGGBB` or `#RRGGBB` color into ARGB channels.
+fn parse_color(color: &str) -> Option<[u8; 4]> {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    let channel =
+        |idx: usize| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok();
+    match hex.len() {
+        8 => Some([channel(0)?, channel(2)?, channel(4)?, channel(6)?]),
+        6 => Some([255, channel(0)?, channel(2)?, channel(4)?]),
+        _ => None,
+    }
+}
+
+/// Returns an error if different names of types, fields of a class or values
+/// of an enum become the same identifier.
+fn check_idents(project: &Project) -> Result<(), CodegenError> {
+    let mut types = HashMap::new();
+    for property_type in project.property_types.iter() {
+        match property_type {
+            PropertyType::Class { name, members, .. } => {
+                insert_ident(&mut types, type_ident(name), name)?;
+                let mut fields = HashMap::new();
+                for member in members {
+                    let field = field_ident(&member.name);
+                    insert_ident(&mut fields, field, &member.name)?;
+                }
+            }
+            PropertyType::Enum {
+                name,
+                values,
+                values_as_flags,
+            } => {
+                insert_ident(&mut types, type_ident(name), name)?;
+                if *values_as_flags {
+                    let flags = format!("{}Flags", type_ident(name));
+                    insert_ident(&mut types, flags, name)?;
+                }
+                let mut variants = HashMap::new();
+                for value in values {
+                    insert_ident(&mut variants, type_ident(value), value)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn insert_ident<'a>(
+    idents: &mut HashMap<String, &'a str>,
+    ident: String,
+    name: &'a str,
+) -> Result<(), CodegenError> {
+    match idents.insert(ident.clone(), name) {
+        Some(first) => Err(CodegenError::NameCollision {
+            first: first.to_string(),
+            second: name.to_string(),
+            ident,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// `UpperCamelCase` identifier from any Tiled name.
+fn type_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut upper = true;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                ident.push(c.to_ascii_uppercase());
+            } else {
+                ident.push(c);
+            }
+            upper = false;
+        } else {
+            upper = true;
+        }
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, 'T');
+    }
+    ident
+}
+
+/// `snake_case` identifier from any Tiled name.
+fn field_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else {
+            if !ident.is_empty() && !ident.ends_with('_') {
+                ident.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    let mut ident = ident.trim_end_matches('_').to_string();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert_str(0, "f_");
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+    "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r##"{
+        "propertyTypes": [
+            {
+                "id": 1,
+                "name": "Stats",
+                "type": "class",
+                "useAs": ["property"],
+                "members": [
+                    { "name": "health", "type": "int", "value": 10 },
+                    {
+                        "name": "element",
+                        "type": "string",
+                        "propertyType": "Element",
+                        "value": "Water"
+                    }
+                ]
+            },
+            {
+                "id": 2,
+                "name": "Ninja",
+                "type": "class",
+                "useAs": ["object", "tile"],
+                "members": [
+                    { "name": "Max Speed", "type": "float", "value": 2 },
+                    {
+                        "name": "stats",
+                        "type": "class",
+                        "propertyType": "Stats",
+                        "value": { "health": 20 }
+                    }
+                ]
+            },
+            {
+                "id": 3,
+                "name": "Element",
+                "type": "enum",
+                "storageType": "string",
+                "values": ["Fire", "Water"],
+                "valuesAsFlags": false
+            }
+        ]
+    }"##;
+
+    #[test]
+    fn test_generate() {
+        let code = generate(PROJECT).unwrap();
+        assert!(code.contains("#[derive(TiledClass, Clone, Debug)]"));
+        assert!(code.contains("pub struct Stats {"));
+        assert!(code.contains("#[derive(Component, TiledComponent, Clone"));
+        assert!(code.contains("#[tiled(default)]\npub struct Ninja {"));
+        assert!(code.contains("#[tiled(rename = \"Max Speed\")]"));
+        assert!(code.contains("pub max_speed: f32,"));
+        assert!(code.contains("max_speed: 2.0,"));
+        assert!(
+            code.contains("stats: Stats { health: 20, ..Default::default() },")
+        );
+        assert!(code.contains("pub element: Element,"));
+        assert!(code.contains("element: Element::Water,"));
+        assert!(code.contains("app.register_tiled_component::<Ninja>();"));
+        assert!(!code.contains("register_tiled_component::<Stats>"));
+    }
+
+    #[test]
+    fn test_name_collision() {
+        let project = r#"{
+            "propertyTypes": [
+                { "id": 1, "name": "fire ball", "type": "class" },
+                { "id": 2, "name": "FireBall", "type": "class" }
+            ]
+        }"#;
+        match generate(project) {
+            Err(CodegenError::NameCollision {
+                first,
+                second,
+                ident,
+            }) => {
+                assert_eq!(first, "fire ball");
+                assert_eq!(second, "FireBall");
+                assert_eq!(ident, "FireBall");
+            }
+            _ => panic!("Collision is not detected"),
+        }
+        let project = r#"{
+            "propertyTypes": [{
+                "id": 1,
+                "name": "Ninja",
+                "type": "class",
+                "members": [
+                    { "name": "Max Speed", "type": "float", "value": 2 },
+                    { "name": "maxSpeed", "type": "float", "value": 3 }
+                ]
+            }]
+        }"#;
+        assert!(matches!(
+            generate(project),
+            Err(CodegenError::NameCollision { .. })
+        ));
+    }
+
+    #[test]
+    fn test_idents() {
+        assert_eq!(type_ident("fire ball"), "FireBall");
+        assert_eq!(type_ident("2d"), "T2d");
+        assert_eq!(field_ident("Max Speed"), "max_speed");
+        assert_eq!(field_ident("maxSpeed"), "max_speed");
+        assert_eq!(field_ident("type"), "type_");
+    }
+}
@@ -0,0 +1,29 @@
+//! Prints or writes Rust types generated from a `.tiled-project` file.
+//!
+//! Usage: `bevy_tiled_toolkit_codegen <project> [output]`
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(project) = args.next() else {
+        eprintln!("Usage: bevy_tiled_toolkit_codegen <project> [output]");
+        return ExitCode::FAILURE;
+    };
+    let result = match args.next() {
+        Some(output) => {
+            bevy_tiled_toolkit_codegen::generate_file(&project, output)
+        }
+        None => std::fs::read_to_string(&project)
+            .map_err(bevy_tiled_toolkit_codegen::CodegenError::from)
+            .and_then(|json| bevy_tiled_toolkit_codegen::generate(&json))
+            .map(|code| print!("{}", code)),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}: {}", project, e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Derive macros for `bevy_tiled_toolkit`, use them through its re-exports.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, LitStr};
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Implements `TiledComponent`, `FromTiledProperties` and `FromTiledProperty`.
+#[proc_macro_derive(TiledComponent, attributes(tiled))]
+pub fn derive_tiled_component(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, true)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromTiledProperties` and `FromTiledProperty`, so the type can
+/// be used as a nested class property.
+#[proc_macro_derive(TiledClass, attributes(tiled))]
+pub fn derive_tiled_class(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input, false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// `#[tiled(...)]` attributes of the struct.
+#[derive(Default)]
+struct StructAttrs {
+    class: Option<LitStr>,
+    default: bool,
+}
+
+/// `#[tiled(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldAttrs {
+    rename: Option<LitStr>,
+    default: bool,
+}
+
+fn expand(input: &DeriveInput, component: bool) -> syn::Result<TokenStream2> {
+    let krate = quote!(::bevy_tiled_toolkit);
+    let bevy = quote!(#krate::__private::bevy);
+    let tiled = quote!(#krate::__private::tiled);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "Tiled derive macros support only structs",
+        ));
+    };
+    let struct_attrs = parse_struct_attrs(&input.attrs)?;
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let mut inits = Vec::new();
+            for field in fields.named.iter() {
+                let ident = field.ident.as_ref().expect("named field");
+                let attrs = parse_field_attrs(&field.attrs)?;
+                let property = attrs.rename.unwrap_or_else(|| {
+                    LitStr::new(&ident.to_string(), ident.span())
+                });
+                // Missing fields are taken from the field type's `Default`,
+                // or from the struct's `Default` if it's marked
+                inits.push(if attrs.default {
+                    quote! {
+                        #ident: #krate::property_value::get_property_or_default(
+                            properties,
+                            #property,
+                            asset_server,
+                        )?
+                    }
+                } else if struct_attrs.default {
+                    quote! {
+                        #ident: #krate::property_value::get_property_or(
+                            properties,
+                            #property,
+                            asset_server,
+                            defaults.#ident,
+                        )?
+                    }
+                } else {
+                    quote! {
+                        #ident: #krate::property_value::get_property(
+                            properties,
+                            #property,
+                            asset_server,
+                        )?
+                    }
+                });
+            }
+            if struct_attrs.default {
+                quote!({
+                    let defaults = <Self as ::std::default::Default>::default();
+                    Self { #(#inits,)* }
+                })
+            } else {
+                quote!(Self { #(#inits,)* })
+            }
+        }
+        Fields::Unit => quote!(Self),
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "Tiled derive macros don't support tuple structs",
+            ))
+        }
+    };
+
+    let mut output = quote! {
+        impl #impl_generics #krate::property_value::FromTiledProperties
+            for #name #ty_generics #where_clause
+        {
+            #[allow(unused_variables)]
+            fn from_tiled_properties(
+                properties: &#tiled::Properties,
+                asset_server: &#bevy::asset::AssetServer,
+            ) -> ::std::result::Result<
+                Self,
+                #krate::property_value::TiledPropertyError,
+            > {
+                ::std::result::Result::Ok(#construct)
+            }
+        }
+
+        impl #impl_generics #krate::property_value::FromTiledProperty
+            for #name #ty_generics #where_clause
+        {
+            fn from_tiled_property(
+                value: &#tiled::PropertyValue,
+                asset_server: &#bevy::asset::AssetServer,
+            ) -> ::std::result::Result<
+                Self,
+                #krate::property_value::TiledPropertyError,
+            > {
+                #krate::property_value::from_class_property(value, asset_server)
+            }
+        }
+    };
+
+    if component {
+        let class = struct_attrs
+            .class
+            .unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+        output.extend(quote! {
+            impl #impl_generics #krate::TiledComponent
+                for #name #ty_generics #where_clause
+            {
+                fn insert_self_to_entity(
+                    &self,
+                    commands: &mut #bevy::ecs::system::EntityCommands,
+                    values: #bevy::utils::HashMap<
+                        ::std::string::String,
+                        #tiled::PropertyValue,
+                    >,
+                    asset_server: &#bevy::ecs::system::Res<
+                        #bevy::asset::AssetServer,
+                    >,
+                ) {
+                    if let ::std::result::Result::Err(e) =
+                        <Self as #krate::TiledComponent>::try_insert_self_to_entity(
+                            self,
+                            commands,
+                            values,
+                            asset_server,
+                        )
+                    {
+                        #bevy::log::error!("Can't insert {}: {}", #class, e);
+                    }
+                }
+
+                fn try_insert_self_to_entity(
+                    &self,
+                    commands: &mut #bevy::ecs::system::EntityCommands,
+                    values: #bevy::utils::HashMap<
+                        ::std::string::String,
+                        #tiled::PropertyValue,
+                    >,
+                    asset_server: &#bevy::ecs::system::Res<
+                        #bevy::asset::AssetServer,
+                    >,
+                ) -> ::std::result::Result<
+                    (),
+                    #krate::property_value::TiledPropertyError,
+                > {
+                    #krate::property_value::insert_from_properties::<Self>(
+                        commands,
+                        values,
+                        asset_server,
+                    )
+                }
+
+                fn get_class_name(&self) -> &str {
+                    #class
+                }
+            }
+        });
+    }
+    Ok(output)
+}
+
+// ───── Utility functions ────────────────────────────────────────────────── //
+
+fn parse_struct_attrs(attrs: &[Attribute]) -> syn::Result<StructAttrs> {
+    let mut result = StructAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("tiled")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("class") {
+                result.class = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                result.default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `class` or `default`"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+fn parse_field_attrs(attrs: &[Attribute]) -> syn::Result<FieldAttrs> {
+    let mut result = FieldAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("tiled")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                result.rename = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                result.default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `rename` or `default`"))
+            }
+        })?;
+    }
+    Ok(result)
+}
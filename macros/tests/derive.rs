@@ -0,0 +1,168 @@
+//! Checks code generated by `#[derive(TiledComponent)]` and
+//! `#[derive(TiledClass)]`.
+
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_tiled_toolkit::__private::tiled;
+use bevy_tiled_toolkit::prelude::*;
+use bevy_tiled_toolkit::property_value::FromTiledProperties;
+
+#[derive(TiledClass, Default, Debug, PartialEq)]
+struct Stats {
+    health: i32,
+    #[tiled(default)]
+    armor: i32,
+}
+
+#[derive(Component, TiledComponent, Debug, PartialEq)]
+#[tiled(class = "Ninja")]
+struct NinjaComponent {
+    strength: f32,
+    #[tiled(rename = "stats")]
+    ninja_stats: Stats,
+    #[tiled(default)]
+    nickname: String,
+}
+
+#[derive(TiledClass, Debug, PartialEq)]
+#[tiled(default)]
+struct Speed {
+    max: f32,
+    min: f32,
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Speed { max: 2., min: 0.5 }
+    }
+}
+
+fn initialize_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    app
+}
+
+fn initialize_properties() -> tiled::Properties {
+    let mut stats = tiled::Properties::new();
+    stats.insert("health".to_string(), tiled::PropertyValue::IntValue(10));
+    let mut properties = tiled::Properties::new();
+    properties.insert(
+        "strength".to_string(),
+        tiled::PropertyValue::FloatValue(1.5),
+    );
+    properties.insert(
+        "stats".to_string(),
+        tiled::PropertyValue::ClassValue {
+            property_type: "Stats".to_string(),
+            properties: stats,
+        },
+    );
+    properties
+}
+
+#[test]
+fn test_rename_and_default() {
+    let app = initialize_app();
+    let asset_server = app.world.resource::<AssetServer>();
+    let ninja = NinjaComponent::from_tiled_properties(
+        &initialize_properties(),
+        asset_server,
+    );
+    assert_eq!(
+        ninja,
+        Ok(NinjaComponent {
+            strength: 1.5,
+            ninja_stats: Stats {
+                health: 10,
+                armor: 0,
+            },
+            nickname: String::new(),
+        })
+    );
+    assert_eq!(ninja.unwrap().get_class_name(), "Ninja");
+}
+
+#[test]
+fn test_struct_default() {
+    let app = initialize_app();
+    let asset_server = app.world.resource::<AssetServer>();
+    let mut properties = tiled::Properties::new();
+    properties.insert("max".to_string(), tiled::PropertyValue::FloatValue(3.));
+    assert_eq!(
+        Speed::from_tiled_properties(&properties, asset_server),
+        Ok(Speed { max: 3., min: 0.5 })
+    );
+}
+
+#[test]
+fn test_errors() {
+    let app = initialize_app();
+    let asset_server = app.world.resource::<AssetServer>();
+
+    let mut properties = initialize_properties();
+    properties.remove("strength");
+    assert_eq!(
+        NinjaComponent::from_tiled_properties(&properties, asset_server),
+        Err(TiledPropertyError::Missing {
+            name: "strength".to_string()
+        })
+    );
+
+    let mut properties = initialize_properties();
+    if let Some(tiled::PropertyValue::ClassValue { properties, .. }) =
+        properties.get_mut("stats")
+    {
+        properties.insert(
+            "health".to_string(),
+            tiled::PropertyValue::StringValue("full".to_string()),
+        );
+    }
+    assert_eq!(
+        NinjaComponent::from_tiled_properties(&properties, asset_server),
+        Err(TiledPropertyError::TypeMismatch {
+            name: "stats.health".to_string(),
+            expected: "int",
+            found: "string",
+        })
+    );
+}
+
+#[test]
+fn test_try_insert() {
+    let mut app = initialize_app();
+    let entity = app.world.spawn_empty().id();
+    let mut state: SystemState<(Commands, Res<AssetServer>)> =
+        SystemState::new(&mut app.world);
+    let (mut commands, asset_server) = state.get_mut(&mut app.world);
+    let component = NinjaComponent {
+        strength: 0.,
+        ninja_stats: Stats::default(),
+        nickname: String::new(),
+    };
+
+    let values: HashMap<_, _> = initialize_properties().into_iter().collect();
+    let mut invalid = values.clone();
+    invalid.remove("strength");
+    let result = component.try_insert_self_to_entity(
+        &mut commands.entity(entity),
+        invalid,
+        &asset_server,
+    );
+    assert_eq!(
+        result,
+        Err(TiledPropertyError::Missing {
+            name: "strength".to_string()
+        })
+    );
+    let result = component.try_insert_self_to_entity(
+        &mut commands.entity(entity),
+        values,
+        &asset_server,
+    );
+    assert_eq!(result, Ok(()));
+    state.apply(&mut app.world);
+    let inserted = app.world.get::<NinjaComponent>(entity).unwrap();
+    assert_eq!(inserted.strength, 1.5);
+}
@@ -1,7 +1,13 @@
+use bevy::ecs::system::EntityCommands;
 use bevy::prelude::*;
 
 // ───── Current Crate Imports ────────────────────────────────────────────── //
 
+use crate::factory::{TiledComponentContext, TiledComponentFactory};
+use crate::renderer::TiledLayerRenderer;
+use crate::resources::TiledLayerHandlerResource;
+use crate::resources::TiledLayerRendererResource;
+use crate::TiledLayerHandler;
 use crate::{resources::TiledComponentResource, TiledComponent};
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
@@ -10,6 +16,36 @@ pub trait TiledComponentReg {
     fn register_tiled_component<T>(&mut self) -> &mut Self
     where
         T: TiledComponent + Default + Send + Sync + 'static;
+
+    fn register_tiled_layer_handler<T>(&mut self) -> &mut Self
+    where
+        T: TiledLayerHandler + Default + Send + Sync + 'static;
+
+    /// Register a closure, function or any other factory, which inserts
+    /// components to tiles and objects with the `class`.
+    fn register_tiled_component_factory<F>(
+        &mut self,
+        class: &str,
+        factory: F,
+    ) -> &mut Self
+    where
+        F: TiledComponentFactory;
+
+    /// Same as `register_tiled_component_factory`, but infers argument types
+    /// of a closure.
+    fn register_tiled_component_fn<F>(
+        &mut self,
+        class: &str,
+        f: F,
+    ) -> &mut Self
+    where
+        F: Fn(
+                &mut EntityCommands,
+                &TiledComponentContext,
+            ) -> anyhow::Result<()>
+            + Send
+            + Sync
+            + 'static;
 }
 
 impl TiledComponentReg for App {
@@ -29,4 +65,92 @@ impl TiledComponentReg for App {
         }
         self
     }
+
+    fn register_tiled_layer_handler<T>(&mut self) -> &mut Self
+    where
+        T: TiledLayerHandler + Default + Send + Sync + 'static,
+    {
+        match self.world.get_resource_mut::<TiledLayerHandlerResource>() {
+            Some(mut res) => {
+                res.vec.push(Box::new(T::default()));
+            }
+            None => {
+                let mut res = TiledLayerHandlerResource::new();
+                res.vec.push(Box::new(T::default()));
+                self.world.insert_resource::<TiledLayerHandlerResource>(res);
+            }
+        }
+        self
+    }
+
+    fn register_tiled_component_factory<F>(
+        &mut self,
+        class: &str,
+        factory: F,
+    ) -> &mut Self
+    where
+        F: TiledComponentFactory,
+    {
+        match self.world.get_resource_mut::<TiledComponentResource>() {
+            Some(mut res) => {
+                res.factories.push((class.to_string(), Box::new(factory)));
+            }
+            None => {
+                let mut res = TiledComponentResource::new();
+                res.factories.push((class.to_string(), Box::new(factory)));
+                self.world.insert_resource::<TiledComponentResource>(res);
+            }
+        }
+        self
+    }
+
+    fn register_tiled_component_fn<F>(&mut self, class: &str, f: F) -> &mut Self
+    where
+        F: Fn(
+                &mut EntityCommands,
+                &TiledComponentContext,
+            ) -> anyhow::Result<()>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.register_tiled_component_factory(class, f)
+    }
+}
+
+pub trait TiledLayerRendererReg {
+    /// Register the renderer, which can be selected by layers with `renderer`
+    /// property or class equal to `name`. Replaces built-in renderers with
+    /// the same name.
+    fn register_layer_renderer<R>(
+        &mut self,
+        name: &str,
+        renderer: R,
+    ) -> &mut Self
+    where
+        R: TiledLayerRenderer;
+}
+
+impl TiledLayerRendererReg for App {
+    fn register_layer_renderer<R>(
+        &mut self,
+        name: &str,
+        renderer: R,
+    ) -> &mut Self
+    where
+        R: TiledLayerRenderer,
+    {
+        match self.world.get_resource_mut::<TiledLayerRendererResource>() {
+            Some(mut res) => {
+                res.renderers.insert(name.to_string(), Box::new(renderer));
+            }
+            None => {
+                let mut res = TiledLayerRendererResource::default();
+                res.renderers.insert(name.to_string(), Box::new(renderer));
+                self.world
+                    .insert_resource::<TiledLayerRendererResource>(res);
+            }
+        }
+        self
+    }
 }
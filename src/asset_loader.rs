@@ -5,17 +5,21 @@ use bevy::prelude::*;
 use bevy::reflect::{TypePath, TypeUuid};
 use bevy::utils::HashMap;
 use std::io::Cursor;
+use std::path::{Component, Path, PathBuf};
 use std::rc::Rc;
 
 // ───── Current Crate Imports ────────────────────────────────────────────── //
 
+#[cfg(feature = "render")]
 use super::components::TilesetTexture;
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
 
+#[cfg(feature = "render")]
 type TilesetIdx = usize;
 
-/// Asset, `Handle<TileMap>` we will load from asset_server
+/// Asset, `Handle<TileMap>` we will load from asset_server. Without `render`
+/// feature it contains only the parsed map, tileset images are not loaded.
 #[derive(TypeUuid, TypePath)]
 #[uuid = "e51081d0-6168-4881-a1c6-4249b2000d7f"]
 pub struct TiledMapAsset {
@@ -23,10 +27,12 @@ pub struct TiledMapAsset {
     pub map: tiled::Map,
     /// Stores pairs of tileset_index and actual `TilesetTexture` container,
     /// loaded from disk.
+    #[cfg(feature = "render")]
     pub tilemap_textures: HashMap<TilesetIdx, TilesetTexture>,
     /// Needs for linking every loaded individual tile-id to it's image.
     /// Contains (tileset_index, tile_id) pairs as `key` and image offset in
     /// `TilesetTexture::Vector(tile_images)` container as `value`.
+    #[cfg(feature = "render")]
     pub tile_image_offsets: HashMap<(TilesetIdx, tiled::TileId), u32>,
     //
     // These are initially empty
@@ -34,14 +40,119 @@ pub struct TiledMapAsset {
     /// When we loaded all individual tile images, we need to pack them all
     /// into single atlases. This field stores correlation between
     /// `tileset_index` and actual `Handle<TextureAtlas>`.
+    #[cfg(feature = "render")]
     pub atlases: HashMap<TilesetIdx, Handle<TextureAtlas>>,
     /// We have to know where every single tile-id placed in it's tileset
     /// atlas for playing animation purposes.
+    #[cfg(feature = "render")]
     pub atlases_offsets: HashMap<TilesetIdx, HashMap<tiled::TileId, usize>>,
+    /// File properties of the map, its layers, objects and tiles, by the
+    /// path written in Tiled. They are loaded as dependencies of the map.
+    pub files: HashMap<String, TiledFile>,
     /// When all atlases are loaded `atlases_loaded` switches to `true`.
     pub(crate) atlases_loaded: bool,
 }
 
+impl TiledMapAsset {
+    /// Returns the handle of a file property by the path written in Tiled.
+    /// `None` if the file isn't loaded or `T` doesn't match its
+    /// [TiledFileKind], e.g. `Handle<Image>` for `.png` files,
+    /// `Handle<AudioSource>` for `.ogg` or `Handle<TiledMapAsset>` for `.tmx`.
+    pub fn file_handle<T: bevy::asset::Asset>(
+        &self,
+        path: &str,
+    ) -> Option<Handle<T>> {
+        let file = self.files.get(path)?;
+        if !file.kind?.is::<T>() {
+            return None;
+        }
+        file.handle.clone().map(HandleUntyped::typed)
+    }
+}
+
+#[cfg(test)]
+impl TiledMapAsset {
+    /// Parses the map without tileset images and file properties.
+    pub(crate) fn from_tmx(tmx: &str) -> Self {
+        let mut loader = tiled::Loader::with_cache_and_reader(
+            tiled::DefaultResourceCache::new(),
+            BytesResourceReader {
+                bytes: Rc::from(tmx.as_bytes()),
+            },
+        );
+        TiledMapAsset {
+            map: loader.load_tmx_map("map.tmx").unwrap(),
+            #[cfg(feature = "render")]
+            tilemap_textures: HashMap::new(),
+            #[cfg(feature = "render")]
+            tile_image_offsets: HashMap::new(),
+            #[cfg(feature = "render")]
+            atlases: HashMap::new(),
+            #[cfg(feature = "render")]
+            atlases_offsets: HashMap::new(),
+            files: HashMap::new(),
+            atlases_loaded: false,
+        }
+    }
+}
+
+/// File from a file property of the map.
+#[derive(Debug, Clone)]
+pub struct TiledFile {
+    /// Asset path, resolved relative to the map.
+    pub path: PathBuf,
+    /// Asset type of the file, `None` if its extension isn't known.
+    pub kind: Option<TiledFileKind>,
+    /// Strong handle of a known file, which is loaded while the map is
+    /// loaded.
+    pub handle: Option<HandleUntyped>,
+}
+
+/// Asset types, which are loaded from file properties with the map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TiledFileKind {
+    /// `.tmx` file, loaded as [TiledMapAsset].
+    Map,
+    /// `.png`, `.jpg`, `.jpeg`, `.bmp`, `.tga` or `.webp` file, loaded as
+    /// [Image] only with `render` feature.
+    #[cfg(feature = "render")]
+    Image,
+    /// `.ogg`, `.wav`, `.mp3` or `.flac` file, loaded as [AudioSource] only
+    /// with `audio` feature.
+    #[cfg(feature = "audio")]
+    Audio,
+}
+
+impl TiledFileKind {
+    /// Returns the kind by the file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "tmx" => Some(TiledFileKind::Map),
+            #[cfg(feature = "render")]
+            "png" | "jpg" | "jpeg" | "bmp" | "tga" | "webp" => {
+                Some(TiledFileKind::Image)
+            }
+            #[cfg(feature = "audio")]
+            "ogg" | "oga" | "spx" | "wav" | "mp3" | "flac" => {
+                Some(TiledFileKind::Audio)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if files of this kind are loaded as `T`.
+    pub fn is<T: bevy::asset::Asset>(self) -> bool {
+        match self {
+            TiledFileKind::Map => T::TYPE_UUID == TiledMapAsset::TYPE_UUID,
+            #[cfg(feature = "render")]
+            TiledFileKind::Image => T::TYPE_UUID == Image::TYPE_UUID,
+            #[cfg(feature = "audio")]
+            TiledFileKind::Audio => T::TYPE_UUID == AudioSource::TYPE_UUID,
+        }
+    }
+}
+
 /// Mock type for piping bytes from `AssetLoader`'s context to
 /// `tiled::Loader` for properly parsing `tmx` format.
 struct BytesResourceReader {
@@ -93,24 +204,47 @@ impl AssetLoader for TiledLoader {
 
             // `tile_image_offsets` contains some strange value: idx from
             // tileset's enumerate(), tile-id and order index of tile
-            let (dependencies, tilemap_textures, tile_image_offsets) =
+            #[cfg(feature = "render")]
+            let (mut dependencies, tilemap_textures, tile_image_offsets) =
                 get_tilemaps_with_deps(&map, load_context);
+            #[cfg(not(feature = "render"))]
+            let mut dependencies = Vec::new();
+
+            // File properties are written relative to the map file, only
+            // files with known asset types are loaded
+            let mut files = HashMap::new();
+            for file in collect_file_properties(&map) {
+                let path = resolve_file_path(load_context.path(), &file);
+                let kind = TiledFileKind::from_path(&path);
+                let handle = kind.map(|_| {
+                    let asset_path = AssetPath::new(path.clone(), None);
+                    let handle =
+                        load_context.get_handle_untyped(asset_path.clone());
+                    dependencies.push(asset_path);
+                    handle
+                });
+                files.insert(file, TiledFile { path, kind, handle });
+            }
 
             let asset_map = TiledMapAsset {
                 map: map.clone(),
+                #[cfg(feature = "render")]
                 tilemap_textures,
+                #[cfg(feature = "render")]
                 atlases: HashMap::new(),
+                #[cfg(feature = "render")]
                 atlases_offsets: HashMap::new(),
+                #[cfg(feature = "render")]
                 tile_image_offsets,
+                files,
                 atlases_loaded: false,
             };
 
             info!("Loaded map: {}", load_context.path().display());
 
-            let loaded_asset = LoadedAsset::new(asset_map);
-            load_context.set_default_asset(
-                loaded_asset.with_dependencies(dependencies),
-            );
+            let loaded_asset =
+                LoadedAsset::new(asset_map).with_dependencies(dependencies);
+            load_context.set_default_asset(loaded_asset);
             Ok(())
         })
     }
@@ -120,6 +254,7 @@ impl AssetLoader for TiledLoader {
     }
 }
 
+#[cfg(feature = "render")]
 fn get_tilemaps_with_deps<'a>(
     map: &tiled::Map,
     load_context: &mut bevy::asset::LoadContext<'_>,
@@ -174,3 +309,153 @@ fn get_tilemaps_with_deps<'a>(
     // `for`
     (dependencies, tilemap_textures, tile_image_offsets)
 }
+
+/// Returns values of all non-empty file properties of the map, including
+/// members of class properties.
+fn collect_file_properties(map: &tiled::Map) -> Vec<String> {
+    fn collect(properties: &tiled::Properties, files: &mut Vec<String>) {
+        for value in properties.values() {
+            match value {
+                tiled::PropertyValue::FileValue(file)
+                    if !file.is_empty() && !files.contains(file) =>
+                {
+                    files.push(file.clone())
+                }
+                tiled::PropertyValue::ClassValue { properties, .. } => {
+                    collect(properties, files)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    collect(&map.properties, &mut files);
+    for tileset in map.tilesets() {
+        collect(&tileset.properties, &mut files);
+        for (_, tile) in tileset.tiles() {
+            collect(&tile.properties, &mut files);
+        }
+    }
+    for layer in map.layers() {
+        collect(&layer.properties, &mut files);
+        if let Some(object_layer) = layer.as_object_layer() {
+            for object in object_layer.objects() {
+                collect(&object.properties, &mut files);
+            }
+        }
+    }
+    files
+}
+
+/// Joins the file path with the directory of the map and removes `.` and
+/// `..` components, so the same file gets the same asset path.
+fn resolve_file_path(map_path: &Path, file: &str) -> PathBuf {
+    let joined = map_path.parent().unwrap_or(Path::new("")).join(file);
+    let mut path = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                path.pop();
+            }
+            component => path.push(component),
+        }
+    }
+    path
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down"
+     width="2" height="2" tilewidth="16" tileheight="16" infinite="0">
+ <properties>
+  <property name="icon" type="file" value="../images/icon.png"/>
+  <property name="background" type="file" value="../images/sky.JPG"/>
+  <property name="music" type="file" value="../audio/theme.ogg"/>
+  <property name="notes" type="file" value="notes.txt"/>
+ </properties>
+ <layer id="1" name="Ground" width="2" height="2">
+  <data encoding="csv">0,0,0,0</data>
+ </layer>
+</map>"#;
+
+    /// Loads the map from a temporary asset folder in an app without render
+    /// plugins. The folder is removed when the returned `TempDir` is dropped.
+    fn load_headless_map() -> (tempfile::TempDir, App, Handle<TiledMapAsset>) {
+        let assets = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(assets.path().join("maps")).unwrap();
+        std::fs::write(assets.path().join("maps/level.tmx"), MAP).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin {
+                asset_folder: assets.path().to_string_lossy().into_owned(),
+                ..default()
+            },
+        ))
+        .add_asset::<TiledMapAsset>()
+        .add_asset_loader(TiledLoader);
+        let handle = app.world.resource::<AssetServer>().load("maps/level.tmx");
+        for _ in 0..10_000 {
+            app.update();
+            let maps = app.world.resource::<Assets<TiledMapAsset>>();
+            if maps.get(&handle).is_some() {
+                return (assets, app, handle);
+            }
+        }
+        panic!("Map is not loaded");
+    }
+
+    #[test]
+    fn test_file_dependencies() {
+        let (_assets, app, handle) = load_headless_map();
+        let maps = app.world.resource::<Assets<TiledMapAsset>>();
+        let map = maps.get(&handle).unwrap();
+
+        let notes = &map.files["notes.txt"];
+        assert_eq!(notes.path, PathBuf::from("maps/notes.txt"));
+        assert_eq!(notes.kind, None);
+        assert!(notes.handle.is_none());
+
+        let icon = &map.files["../images/icon.png"];
+        assert_eq!(icon.path, PathBuf::from("images/icon.png"));
+        assert!(map
+            .file_handle::<TiledMapAsset>("../images/icon.png")
+            .is_none());
+        let background = &map.files["../images/sky.JPG"];
+        let music = &map.files["../audio/theme.ogg"];
+        assert_eq!(music.path, PathBuf::from("audio/theme.ogg"));
+        #[cfg(feature = "render")]
+        {
+            assert_eq!(icon.kind, Some(TiledFileKind::Image));
+            assert_eq!(background.kind, Some(TiledFileKind::Image));
+            assert!(map.file_handle::<Image>("../images/icon.png").is_some());
+            assert!(map.file_handle::<Image>("../images/sky.JPG").is_some());
+        }
+        #[cfg(not(feature = "render"))]
+        {
+            assert_eq!(icon.kind, None);
+            assert!(icon.handle.is_none());
+            assert!(background.handle.is_none());
+        }
+        #[cfg(feature = "audio")]
+        {
+            assert_eq!(music.kind, Some(TiledFileKind::Audio));
+            assert!(map
+                .file_handle::<AudioSource>("../audio/theme.ogg")
+                .is_some());
+        }
+        #[cfg(not(feature = "audio"))]
+        {
+            assert_eq!(music.kind, None);
+            assert!(music.handle.is_none());
+        }
+    }
+}
@@ -5,12 +5,19 @@ use bevy::utils::HashMap;
 
 // ───── Current Crate Imports ────────────────────────────────────────────── //
 
-pub use storages::{LayerStorage, TileStorage, TileStorageError};
-pub use tile_pos::TilePos;
+pub use properties::TiledProperties;
+pub use storages::{
+    LayerStorage, ObjectStorage, TileLayerId, TileStorage, TileStorageError,
+    TilesIter,
+};
+pub use tile_offset::{CardinalDirection, OrdinalDirection, TileOffset};
+pub use tile_pos::{TileLine, TilePos};
 
 // ───── Submodules ───────────────────────────────────────────────────────── //
 
+mod properties;
 mod storages;
+mod tile_offset;
 mod tile_pos;
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
@@ -35,8 +42,72 @@ mod tile_pos;
 #[derive(Component)]
 pub struct DespawnTiledMap;
 
+/// Inserted to every layer entity of the map.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TiledLayer {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    /// Index of the layer in the map.
+    pub index: usize,
+}
+
+/// Marks tile layers, which place tile centers at grid points of the layer
+/// entity, like `bevy_ecs_tilemap` does. Without it, the bottom-left corner
+/// of the first tile is at the origin of the layer.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct CenteredTileGrid;
+
+/// Inserted to every object spawned from an object layer.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TiledObject {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    /// Object id from Tiled.
+    pub id: u32,
+}
+
+/// Object, which is referenced by the object property of this object. It is
+/// inserted only if the object has a single object property, see
+/// [TiledObjectRefs] for all references.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Lever;
+///
+/// fn system_pull_lever(
+///     lever_query: Query<&TiledObjectRef, With<Lever>>,
+///     mut door_query: Query<&mut Visibility>,
+/// ) {
+///     for door in lever_query.iter() {
+///         if let Ok(mut visibility) = door_query.get_mut(door.0) {
+///             *visibility = Visibility::Hidden;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TiledObjectRef(pub Entity);
+
+/// Objects referenced by object properties of this object, by property name.
+/// Members of class properties are named with dotted paths, like
+/// `trigger.target`.
+#[derive(Component, Default, Clone, Debug, PartialEq, Eq)]
+pub struct TiledObjectRefs {
+    pub refs: HashMap<String, Entity>,
+}
+
+impl TiledObjectRefs {
+    /// Returns the referenced object entity by property name.
+    pub fn get(&self, property: &str) -> Option<Entity> {
+        self.refs.get(property).copied()
+    }
+}
+
 /// Represents unified container for tilesets from single spritesheets and
 /// inidividual images.
+#[cfg(feature = "render")]
 #[derive(Component, Reflect, Clone, Debug, Hash, PartialEq, Eq)]
 #[reflect(Component)]
 pub enum TilesetTexture {
@@ -46,6 +117,7 @@ pub enum TilesetTexture {
     Vector(Vec<Handle<Image>>),
 }
 
+#[cfg(feature = "render")]
 impl Default for TilesetTexture {
     fn default() -> Self {
         TilesetTexture::Single(Default::default())
@@ -53,6 +125,7 @@ impl Default for TilesetTexture {
 }
 
 /// Missing documentation
+#[cfg(feature = "render")]
 #[derive(Component)]
 pub struct Animation {
     /// Missing documentation
@@ -65,5 +138,37 @@ pub struct Animation {
     pub timer: Timer,
 }
 
-#[derive(Component)]
-pub(crate) struct NeedToSpawn;
+/// Load lifecycle of a single tilemap entity. Each map progresses
+/// independently, so you can wait for one map without being blocked by
+/// another map's slow image loading.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_setup_camera(
+///     tilemap_query: Query<&TiledMapStatus, Changed<TiledMapStatus>>,
+/// ) {
+///     for status in tilemap_query.iter() {
+///         if *status == TiledMapStatus::Ready {
+///             // All layers of the map are spawned.
+///         }
+///     }
+/// }
+/// ```
+#[derive(
+    Component, Reflect, Default, Clone, Copy, Debug, Hash, PartialEq, Eq,
+)]
+#[reflect(Component)]
+pub enum TiledMapStatus {
+    /// Waiting for `TiledMapAsset` to be loaded.
+    #[default]
+    Loading,
+    /// Waiting for tileset images and packing them into atlases.
+    BuildingAtlases,
+    /// Atlases are ready, layers are going to be spawned.
+    Spawning,
+    /// All layers are spawned.
+    Ready,
+    /// Map or its images can't be loaded, or the map is not supported.
+    Failed,
+}
@@ -0,0 +1,322 @@
+//! This module contains `TiledProperties` type.
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledFile;
+use crate::project::{TiledEnumValue, TiledProjectAsset};
+use crate::property_value::{
+    property_type_name, FromTiledProperty, TiledPropertyError,
+};
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Class and custom properties from Tiled. It is inserted to the map entity
+/// with properties of the map itself, and passed to
+/// [TiledComponentFactory](crate::factory::TiledComponentFactory).
+///
+/// Typed getters accept a dotted path to the member of a class property, like
+/// `stats.health`, and return [TiledPropertyError] if the property is missing
+/// or has another type.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_play_music(
+///     map_query: Query<&TiledProperties, Added<TiledProperties>>,
+/// ) {
+///     for properties in map_query.iter() {
+///         match properties.get_string("music") {
+///             Ok(track) => info!("Playing {}", track),
+///             Err(e) => warn!("{}", e),
+///         }
+///         // Members of class properties are accessed with dotted paths
+///         let volume = properties.get_f32("mixer.volume").unwrap_or(1.);
+///         info!("Volume {}", volume);
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Clone, Debug, PartialEq)]
+pub struct TiledProperties {
+    /// `Class` field from Tiled.
+    pub class: Option<String>,
+    pub properties: HashMap<String, tiled::PropertyValue>,
+}
+
+impl TiledProperties {
+    /// Create new `TiledProperties` from the class and properties of a Tiled
+    /// element.
+    pub fn new(class: Option<&str>, properties: &tiled::Properties) -> Self {
+        TiledProperties {
+            class: class
+                .filter(|class| !class.is_empty())
+                .map(|class| class.to_string()),
+            properties: properties
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Merges defaults of the class and of nested class properties from the
+    /// project.
+    pub(crate) fn with_defaults(
+        mut self,
+        project: Option<&TiledProjectAsset>,
+    ) -> Self {
+        if let Some(project) = project {
+            project.apply_defaults(self.class.as_deref(), &mut self.properties);
+        }
+        self
+    }
+
+    /// Replaces paths of file properties, which are relative to the map, with
+    /// asset paths resolved when the map was loaded.
+    pub(crate) fn with_files(
+        mut self,
+        files: &HashMap<String, TiledFile>,
+    ) -> Self {
+        fn resolve<'a>(
+            values: impl Iterator<Item = &'a mut tiled::PropertyValue>,
+            files: &HashMap<String, TiledFile>,
+        ) {
+            for value in values {
+                match value {
+                    tiled::PropertyValue::FileValue(path) => {
+                        if let Some(file) = files.get(path.as_str()) {
+                            *path = file.path.to_string_lossy().into_owned();
+                        }
+                    }
+                    tiled::PropertyValue::ClassValue { properties, .. } => {
+                        resolve(properties.values_mut(), files)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        resolve(self.properties.values_mut(), files);
+        self
+    }
+
+    /// Returns the raw property value by name.
+    pub fn get(&self, name: &str) -> Option<&tiled::PropertyValue> {
+        self.properties.get(name)
+    }
+
+    /// Returns the raw property value by dotted path.
+    pub fn get_path(
+        &self,
+        path: &str,
+    ) -> Result<&tiled::PropertyValue, TiledPropertyError> {
+        let mut segments = path.split('.');
+        let first = segments.next().unwrap_or_default();
+        let mut value = self.properties.get(first).ok_or_else(|| {
+            TiledPropertyError::Missing {
+                name: first.to_string(),
+            }
+        })?;
+        let mut walked = first.len();
+        for segment in segments {
+            let tiled::PropertyValue::ClassValue { properties, .. } = value
+            else {
+                return Err(TiledPropertyError::TypeMismatch {
+                    name: path[..walked].to_string(),
+                    expected: "class",
+                    found: property_type_name(value),
+                });
+            };
+            walked += segment.len() + 1;
+            value = properties.get(segment).ok_or_else(|| {
+                TiledPropertyError::Missing {
+                    name: path[..walked].to_string(),
+                }
+            })?;
+        }
+        Ok(value)
+    }
+
+    /// Float property, int properties are converted too.
+    pub fn get_f32(&self, path: &str) -> Result<f32, TiledPropertyError> {
+        self.get_typed(path, "float", |value| match value {
+            tiled::PropertyValue::FloatValue(v) => Some(*v),
+            tiled::PropertyValue::IntValue(v) => Some(*v as f32),
+            _ => None,
+        })
+    }
+
+    pub fn get_i32(&self, path: &str) -> Result<i32, TiledPropertyError> {
+        self.get_typed(path, "int", |value| match value {
+            tiled::PropertyValue::IntValue(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    pub fn get_bool(&self, path: &str) -> Result<bool, TiledPropertyError> {
+        self.get_typed(path, "bool", |value| match value {
+            tiled::PropertyValue::BoolValue(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    pub fn get_string(&self, path: &str) -> Result<&str, TiledPropertyError> {
+        self.get_typed(path, "string", |value| match value {
+            tiled::PropertyValue::StringValue(v) => Some(v.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Asset path of a file property, resolved relative to the map.
+    pub fn get_file(&self, path: &str) -> Result<&str, TiledPropertyError> {
+        self.get_typed(path, "file", |value| match value {
+            tiled::PropertyValue::FileValue(v) => Some(v.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Id of the object referenced by an object property, `0` if unset.
+    pub fn get_object(&self, path: &str) -> Result<u32, TiledPropertyError> {
+        self.get_typed(path, "object", |value| match value {
+            tiled::PropertyValue::ObjectValue(v) => Some(*v),
+            _ => None,
+        })
+    }
+
+    #[cfg(feature = "render")]
+    pub fn get_color(&self, path: &str) -> Result<Color, TiledPropertyError> {
+        self.get_typed(path, "color", |value| match value {
+            tiled::PropertyValue::ColorValue(v) => {
+                Some(crate::plugin::tiled_color_to_bevy(v))
+            }
+            _ => None,
+        })
+    }
+
+    /// Members of a class property, with the property type as the class.
+    pub fn get_class(
+        &self,
+        path: &str,
+    ) -> Result<TiledProperties, TiledPropertyError> {
+        self.get_typed(path, "class", |value| match value {
+            tiled::PropertyValue::ClassValue {
+                property_type,
+                properties,
+            } => Some(TiledProperties::new(Some(property_type), properties)),
+            _ => None,
+        })
+    }
+
+    /// Enum property, which type is declared for the member of this class in
+    /// the project.
+    pub fn get_enum(
+        &self,
+        path: &str,
+        project: &TiledProjectAsset,
+    ) -> Result<TiledEnumValue, TiledPropertyError> {
+        let value = self.get_path(path)?;
+        self.class
+            .as_deref()
+            .and_then(|class| project.member_type(class, path))
+            .and_then(|enum_name| project.enum_value(enum_name, value))
+            .ok_or_else(|| {
+                TiledPropertyError::mismatch("enum", value).with_parent(path)
+            })
+    }
+
+    /// Loads the asset from a file property, the path is already resolved
+    /// relative to the map.
+    pub fn get_handle<T: bevy::asset::Asset>(
+        &self,
+        path: &str,
+        asset_server: &AssetServer,
+    ) -> Result<Handle<T>, TiledPropertyError> {
+        self.get_as(path, asset_server)
+    }
+
+    /// Converts the property with [FromTiledProperty], e.g. to a
+    /// `Handle<Image>` or a `#[derive(TiledClass)]` struct.
+    pub fn get_as<T: FromTiledProperty>(
+        &self,
+        path: &str,
+        asset_server: &AssetServer,
+    ) -> Result<T, TiledPropertyError> {
+        let value = self.get_path(path)?;
+        T::from_tiled_property(value, asset_server)
+            .map_err(|e| e.with_parent(path))
+    }
+
+    fn get_typed<'a, T>(
+        &'a self,
+        path: &str,
+        expected: &'static str,
+        convert: impl FnOnce(&'a tiled::PropertyValue) -> Option<T>,
+    ) -> Result<T, TiledPropertyError> {
+        let value = self.get_path(path)?;
+        convert(value).ok_or_else(|| {
+            TiledPropertyError::mismatch(expected, value).with_parent(path)
+        })
+    }
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn initialize_properties() -> TiledProperties {
+        let mut stats = tiled::Properties::new();
+        stats.insert("health".to_string(), tiled::PropertyValue::IntValue(10));
+        let mut properties = tiled::Properties::new();
+        properties.insert(
+            "stats".to_string(),
+            tiled::PropertyValue::ClassValue {
+                property_type: "Stats".to_string(),
+                properties: stats,
+            },
+        );
+        properties
+            .insert("speed".to_string(), tiled::PropertyValue::FloatValue(1.5));
+        TiledProperties::new(Some("Ninja"), &properties)
+    }
+
+    #[test]
+    fn test_get_typed() {
+        let properties = initialize_properties();
+        assert_eq!(properties.get_f32("speed"), Ok(1.5));
+        assert_eq!(
+            properties.get_i32("speed"),
+            Err(TiledPropertyError::TypeMismatch {
+                name: "speed".to_string(),
+                expected: "int",
+                found: "float",
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_path() {
+        let properties = initialize_properties();
+        assert_eq!(properties.get_i32("stats.health"), Ok(10));
+        assert_eq!(properties.get_f32("stats.health"), Ok(10.));
+        assert_eq!(
+            properties.get_class("stats").map(|stats| stats.class),
+            Ok(Some("Stats".to_string()))
+        );
+        assert_eq!(
+            properties.get_i32("stats.armor"),
+            Err(TiledPropertyError::Missing {
+                name: "stats.armor".to_string(),
+            })
+        );
+        assert_eq!(
+            properties.get_i32("speed.value"),
+            Err(TiledPropertyError::TypeMismatch {
+                name: "speed".to_string(),
+                expected: "class",
+                found: "float",
+            })
+        );
+    }
+}
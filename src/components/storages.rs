@@ -1,4 +1,4 @@
-//! This module contains two types: `LayerStorage` and `TileStorage`.
+//! This module contains storages of layer, tile and object entities.
 
 use std::error::Error;
 use std::fmt::Display;
@@ -8,6 +8,7 @@ use bevy::utils::HashMap;
 
 // ───── Current Crate Imports ────────────────────────────────────────────── //
 
+use super::tile_offset::{CardinalDirection, OrdinalDirection, TileOffset};
 use super::tile_pos::TilePos;
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
@@ -15,27 +16,116 @@ use super::tile_pos::TilePos;
 type LayerIdx = usize;
 type TilemapSize = UVec2;
 
-/// Stores all layers entities by name.
+/// Iterator over positions and entities of tiles.
+pub type TilesIter<'a> = Box<dyn Iterator<Item = (TilePos, Entity)> + 'a>;
+
+/// Stores all layers entities by name, and by index with their Tiled id and
+/// class.
 #[derive(Component, Default, Debug)]
 pub struct LayerStorage {
     pub layers: HashMap<Name, Entity>,
+    by_index: HashMap<LayerIdx, StoredLayer>,
+}
+
+#[derive(Debug)]
+struct StoredLayer {
+    entity: Entity,
+    id: u32,
+    name: String,
+    class: Option<String>,
 }
 
 /// Stores all tiles entities of all layers of the map, (except objects).
+/// Layers spawned with sprites and with `bevy_ecs_tilemap` are stored the
+/// same way, and can be accessed by index or by name:
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_find_chests(tilemap_query: Query<&TileStorage>) {
+///     for tile_storage in tilemap_query.iter() {
+///         for (tile_pos, entity) in tile_storage.iter_layer_tiles("Chests") {
+///             info!("Chest {:?} at {:?}", entity, tile_pos);
+///         }
+///     }
+/// }
+/// ```
 #[derive(Component, Default, Debug)]
 pub struct TileStorage {
     tiles: HashMap<LayerIdx, (TilemapSize, Vec<Option<Entity>>)>,
-    pub(crate) bevy_ecs_tilemap_tile_storages:
-        HashMap<LayerIdx, bevy_ecs_tilemap::prelude::TileStorage>,
+    names: HashMap<String, LayerIdx>,
+}
+
+/// Tile layer of [TileStorage], by index or by name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TileLayerId<'a> {
+    Index(usize),
+    Name(&'a str),
+}
+
+impl From<usize> for TileLayerId<'_> {
+    fn from(idx: usize) -> Self {
+        TileLayerId::Index(idx)
+    }
+}
+
+impl<'a> From<&'a str> for TileLayerId<'a> {
+    fn from(name: &'a str) -> Self {
+        TileLayerId::Name(name)
+    }
+}
+
+impl<'a> From<&'a String> for TileLayerId<'a> {
+    fn from(name: &'a String) -> Self {
+        TileLayerId::Name(name)
+    }
+}
+
+/// Stores all objects entities of the map by Tiled object id, with lookups by
+/// name, class and layer. Despawned objects are removed from it.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_open_doors(
+///     tilemap_query: Query<&ObjectStorage>,
+///     mut door_query: Query<&mut Visibility>,
+/// ) {
+///     for object_storage in tilemap_query.iter() {
+///         for door in object_storage.get_by_class("Door") {
+///             if let Ok(mut visibility) = door_query.get_mut(door) {
+///                 *visibility = Visibility::Hidden;
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Default, Debug)]
+pub struct ObjectStorage {
+    objects: HashMap<u32, StoredObject>,
+    entities: HashMap<Entity, u32>,
+    by_name: HashMap<String, Vec<u32>>,
+    by_class: HashMap<String, Vec<u32>>,
+    by_layer: HashMap<LayerIdx, Vec<u32>>,
+}
+
+#[derive(Debug)]
+struct StoredObject {
+    entity: Entity,
+    layer_idx: LayerIdx,
+    name: String,
+    class: String,
 }
 
 /// Errors which can be returned when working with `TileStorage` type.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TileStorageError {
     /// Layer with this index was already initialized.
     LayerAlreadyInitialized,
     /// There are no layer with this index.
     NoLayerWithIndex,
+    /// There are no tile layer with this name.
+    NoLayerWithName,
     /// The tile's reserved cell is existing, but empty.
     TileCellEmpty,
     /// The tile position lies within the underlying tile layer's extents.
@@ -47,7 +137,7 @@ impl TileStorage {
     pub fn new() -> Self {
         TileStorage {
             tiles: HashMap::new(),
-            bevy_ecs_tilemap_tile_storages: HashMap::new(),
+            names: HashMap::new(),
         }
     }
 
@@ -65,12 +155,49 @@ impl TileStorage {
         Ok(())
     }
 
+    /// Names the layer, so it can be accessed by name. If several layers have
+    /// the same name, the first named one is used.
+    pub fn set_layer_name(&mut self, layer_idx: usize, name: &str) {
+        self.names.entry(name.to_string()).or_insert(layer_idx);
+    }
+
+    /// Returns index of the tile layer with this name.
+    pub fn layer_index<'a>(
+        &self,
+        layer: impl Into<TileLayerId<'a>>,
+    ) -> Result<usize, TileStorageError> {
+        match layer.into() {
+            TileLayerId::Index(idx) if self.tiles.contains_key(&idx) => Ok(idx),
+            TileLayerId::Index(_) => Err(TileStorageError::NoLayerWithIndex),
+            TileLayerId::Name(name) => self
+                .names
+                .get(name)
+                .copied()
+                .ok_or(TileStorageError::NoLayerWithName),
+        }
+    }
+
+    /// Returns size of the tile layer in tiles.
+    pub fn layer_size<'a>(
+        &self,
+        layer: impl Into<TileLayerId<'a>>,
+    ) -> Result<UVec2, TileStorageError> {
+        let layer_idx = self.layer_index(layer)?;
+        Ok(self.tiles[&layer_idx].0)
+    }
+
+    /// Returns indices of all tile layers, not ordered.
+    pub fn layers(&self) -> impl Iterator<Item = usize> + '_ {
+        self.tiles.keys().copied()
+    }
+
     /// Gets a tile entity for the given tile position, if an entity is associated with that tile
-    pub fn get(
+    pub fn get<'a>(
         &self,
-        layer_idx: usize,
+        layer: impl Into<TileLayerId<'a>>,
         tile_pos: &TilePos,
     ) -> Result<Entity, TileStorageError> {
+        let layer_idx = self.layer_index(layer)?;
         if let Some((size, vec)) = self.tiles.get(&layer_idx) {
             if tile_pos.within_map_bounds(*size) {
                 if let Some(entity) = vec[tile_pos.to_index(*size)] {
@@ -109,17 +236,143 @@ impl TileStorage {
     }
 
     /// Returns an iterator with all of the entities of the layer.
-    pub fn iter_layer<'a>(
+    pub fn iter_layer<'a, 'b>(
         &'a self,
-        layer_idx: usize,
+        layer: impl Into<TileLayerId<'b>>,
     ) -> Box<dyn Iterator<Item = &Option<Entity>> + 'a> {
-        if let Some((_, vec)) = self.tiles.get(&layer_idx) {
+        let tiles = self
+            .layer_index(layer)
+            .ok()
+            .and_then(|layer_idx| self.tiles.get(&layer_idx));
+        if let Some((_, vec)) = tiles {
             Box::new(vec.iter())
         } else {
             Box::new(std::iter::empty())
         }
     }
 
+    /// Returns an iterator with positions and entities of all tiles of the
+    /// layer, empty cells are skipped.
+    pub fn iter_layer_tiles<'a, 'b>(
+        &'a self,
+        layer: impl Into<TileLayerId<'b>>,
+    ) -> TilesIter<'a> {
+        let tiles = self
+            .layer_index(layer)
+            .ok()
+            .and_then(|layer_idx| self.tiles.get(&layer_idx));
+        if let Some((size, vec)) = tiles {
+            Box::new(vec.iter().enumerate().filter_map(|(idx, entity)| {
+                entity.map(|entity| (TilePos::from_index(idx, *size), entity))
+            }))
+        } else {
+            Box::new(std::iter::empty())
+        }
+    }
+
+    /// Returns an iterator with layer indices, positions and entities of all
+    /// tiles of the map, not ordered.
+    pub fn iter_all_tiles(
+        &self,
+    ) -> impl Iterator<Item = (usize, TilePos, Entity)> + '_ {
+        self.tiles.iter().flat_map(|(layer_idx, (size, vec))| {
+            vec.iter().enumerate().filter_map(move |(idx, entity)| {
+                entity.map(|entity| {
+                    (*layer_idx, TilePos::from_index(idx, *size), entity)
+                })
+            })
+        })
+    }
+
+    /// Returns orthogonal neighbours of the tile, which are not empty.
+    pub fn neighbours_4<'a, 'b>(
+        &'a self,
+        layer: impl Into<TileLayerId<'b>>,
+        tile_pos: &TilePos,
+    ) -> Result<TilesIter<'a>, TileStorageError> {
+        let offsets = CardinalDirection::ALL.map(TileOffset::from);
+        self.neighbours(layer.into(), tile_pos, offsets.into_iter())
+    }
+
+    /// Returns orthogonal and diagonal neighbours of the tile, which are not
+    /// empty.
+    pub fn neighbours_8<'a, 'b>(
+        &'a self,
+        layer: impl Into<TileLayerId<'b>>,
+        tile_pos: &TilePos,
+    ) -> Result<TilesIter<'a>, TileStorageError> {
+        let offsets = OrdinalDirection::ALL.map(TileOffset::from);
+        self.neighbours(layer.into(), tile_pos, offsets.into_iter())
+    }
+
+    /// Returns not empty tiles of the rectangle between `min` and `max`
+    /// corners inclusively, row by row from the bottom one. Both corners
+    /// should lie within the layer.
+    pub fn region<'a, 'b>(
+        &'a self,
+        layer: impl Into<TileLayerId<'b>>,
+        min: &TilePos,
+        max: &TilePos,
+    ) -> Result<TilesIter<'a>, TileStorageError> {
+        let (size, tiles) = self.layer_tiles(layer.into())?;
+        if !min.within_map_bounds(size) || !max.within_map_bounds(size) {
+            return Err(TileStorageError::TileOutOfLayer);
+        }
+        let (min_x, max_x) = (min.x.min(max.x), min.x.max(max.x));
+        let (min_y, max_y) = (min.y.min(max.y), min.y.max(max.y));
+        Ok(Box::new((min_y..=max_y).flat_map(move |y| {
+            (min_x..=max_x).filter_map(move |x| {
+                let tile_pos = TilePos::new(x, y);
+                tiles[tile_pos.to_index(size)].map(|entity| (tile_pos, entity))
+            })
+        })))
+    }
+
+    /// Returns tiles connected to the `start` tile through orthogonal
+    /// neighbours, for which `predicate` returns `true`. The `start` tile
+    /// is checked too, so the result is empty if it doesn't match.
+    pub fn flood_fill<'a, F>(
+        &self,
+        layer: impl Into<TileLayerId<'a>>,
+        start: &TilePos,
+        mut predicate: F,
+    ) -> Result<Vec<(TilePos, Entity)>, TileStorageError>
+    where
+        F: FnMut(TilePos, Entity) -> bool,
+    {
+        let layer = layer.into();
+        let (size, tiles) = self.layer_tiles(layer)?;
+        if !start.within_map_bounds(size) {
+            return Err(TileStorageError::TileOutOfLayer);
+        }
+        let Some(start_entity) = tiles[start.to_index(size)] else {
+            return Err(TileStorageError::TileCellEmpty);
+        };
+
+        let mut visited = vec![false; tiles.len()];
+        visited[start.to_index(size)] = true;
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back((*start, start_entity));
+        let mut filled = Vec::new();
+        while let Some((tile_pos, entity)) = queue.pop_front() {
+            if !predicate(tile_pos, entity) {
+                continue;
+            }
+            filled.push((tile_pos, entity));
+            let offsets = CardinalDirection::ALL.map(TileOffset::from);
+            for neighbour in
+                self.neighbours(layer, &tile_pos, offsets.into_iter())?
+            {
+                let idx = neighbour.0.to_index(size);
+                if !visited[idx] {
+                    visited[idx] = true;
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        Ok(filled)
+    }
+
     /// Returns an mutable iterator with all of the entities of the layer.
     pub fn iter_mut_layer<'a>(
         &'a mut self,
@@ -146,11 +399,12 @@ impl TileStorage {
 
     /// Remove any stored entity at the given tile position, if the given `tile_pos` does lie within
     /// the extents of the underlying map.
-    pub fn remove_at_layer(
+    pub fn remove_at_layer<'a>(
         &mut self,
-        layer_idx: usize,
+        layer: impl Into<TileLayerId<'a>>,
         tile_pos: &TilePos,
     ) -> Result<Option<Entity>, TileStorageError> {
+        let layer_idx = self.layer_index(layer)?;
         if let Some((size, vec)) = self.tiles.get_mut(&layer_idx) {
             if tile_pos.within_map_bounds(*size) {
                 Ok(vec[tile_pos.to_index(*size)].take())
@@ -165,6 +419,230 @@ impl TileStorage {
     /// Clear all entities from storage.
     pub fn clear(&mut self) {
         self.tiles.clear();
+        self.names.clear();
+    }
+
+    fn layer_tiles(
+        &self,
+        layer: TileLayerId,
+    ) -> Result<(UVec2, &[Option<Entity>]), TileStorageError> {
+        let layer_idx = self.layer_index(layer)?;
+        let (size, tiles) = &self.tiles[&layer_idx];
+        Ok((*size, tiles))
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        layer: TileLayerId,
+        tile_pos: &TilePos,
+        offsets: impl Iterator<Item = TileOffset> + 'a,
+    ) -> Result<TilesIter<'a>, TileStorageError> {
+        let (size, tiles) = self.layer_tiles(layer)?;
+        if !tile_pos.within_map_bounds(size) {
+            return Err(TileStorageError::TileOutOfLayer);
+        }
+        let center = *tile_pos;
+        Ok(Box::new(offsets.filter_map(move |offset| {
+            let tile_pos = center.checked_add(offset, size)?;
+            tiles[tile_pos.to_index(size)].map(|entity| (tile_pos, entity))
+        })))
+    }
+}
+
+impl LayerStorage {
+    /// Create new `LayerStorage`
+    pub fn new() -> Self {
+        LayerStorage::default()
+    }
+
+    /// Gets a layer entity by index of the layer in the map.
+    pub fn get(&self, layer_idx: usize) -> Option<Entity> {
+        self.by_index.get(&layer_idx).map(|layer| layer.entity)
+    }
+
+    /// Gets Tiled layer id by index of the layer in the map.
+    pub fn id_of(&self, layer_idx: usize) -> Option<u32> {
+        self.by_index.get(&layer_idx).map(|layer| layer.id)
+    }
+
+    /// Gets name of the layer by index of the layer in the map.
+    pub fn name_of(&self, layer_idx: usize) -> Option<&str> {
+        self.by_index
+            .get(&layer_idx)
+            .map(|layer| layer.name.as_str())
+    }
+
+    /// Gets class of the layer by index of the layer in the map.
+    pub fn class_of(&self, layer_idx: usize) -> Option<&str> {
+        self.by_index.get(&layer_idx)?.class.as_deref()
+    }
+
+    /// Returns an iterator with indices and entities of all layers, not
+    /// ordered.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Entity)> + '_ {
+        self.by_index
+            .iter()
+            .map(|(layer_idx, layer)| (*layer_idx, layer.entity))
+    }
+
+    /// Stores the layer entity, replacing the layer with the same index.
+    pub fn set(
+        &mut self,
+        layer_idx: usize,
+        entity: Entity,
+        id: u32,
+        name: &str,
+        class: Option<&str>,
+    ) {
+        self.layers.insert(Name::new(name.to_string()), entity);
+        self.by_index.insert(
+            layer_idx,
+            StoredLayer {
+                entity,
+                id,
+                name: name.to_string(),
+                class: class.map(str::to_string),
+            },
+        );
+    }
+
+    /// Clear all entities from storage.
+    pub fn clear(&mut self) {
+        self.layers.clear();
+        self.by_index.clear();
+    }
+}
+
+impl ObjectStorage {
+    /// Create new `ObjectStorage`
+    pub fn new() -> Self {
+        ObjectStorage::default()
+    }
+
+    /// Gets an object entity by Tiled object id.
+    pub fn get(&self, id: u32) -> Option<Entity> {
+        self.objects.get(&id).map(|object| object.entity)
+    }
+
+    /// Gets Tiled object id of the object entity.
+    pub fn get_id(&self, entity: Entity) -> Option<u32> {
+        self.entities.get(&entity).copied()
+    }
+
+    /// Gets name of the object by Tiled object id.
+    pub fn name_of(&self, id: u32) -> Option<&str> {
+        self.objects.get(&id).map(|object| object.name.as_str())
+    }
+
+    /// Gets class of the object by Tiled object id.
+    pub fn class_of(&self, id: u32) -> Option<&str> {
+        self.objects.get(&id).map(|object| object.class.as_str())
+    }
+
+    /// Returns entities of all objects with this name, Tiled doesn't require
+    /// names to be unique.
+    pub fn get_by_name<'a>(
+        &'a self,
+        name: &str,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.entities_of(self.by_name.get(name))
+    }
+
+    /// Returns entities of all objects with this class.
+    pub fn get_by_class<'a>(
+        &'a self,
+        class: &str,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.entities_of(self.by_class.get(class))
+    }
+
+    /// Returns an iterator with object ids and entities of the layer, in the
+    /// order of spawning.
+    pub fn iter_layer(
+        &self,
+        layer_idx: usize,
+    ) -> impl Iterator<Item = (u32, Entity)> + '_ {
+        self.by_layer
+            .get(&layer_idx)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.get(*id).map(|entity| (*id, entity)))
+    }
+
+    /// Stores the object entity, replacing the object with the same id. If
+    /// the entity is already stored under another id, that id is removed.
+    pub fn set(
+        &mut self,
+        id: u32,
+        entity: Entity,
+        layer_idx: usize,
+        name: &str,
+        class: &str,
+    ) {
+        if let Some(old_id) = self.get_id(entity) {
+            self.remove(old_id);
+        }
+        self.remove(id);
+        self.entities.insert(entity, id);
+        self.by_name.entry(name.to_string()).or_default().push(id);
+        self.by_class.entry(class.to_string()).or_default().push(id);
+        self.by_layer.entry(layer_idx).or_default().push(id);
+        self.objects.insert(
+            id,
+            StoredObject {
+                entity,
+                layer_idx,
+                name: name.to_string(),
+                class: class.to_string(),
+            },
+        );
+    }
+
+    /// Removes the object by Tiled object id, returns its entity.
+    pub fn remove(&mut self, id: u32) -> Option<Entity> {
+        let object = self.objects.remove(&id)?;
+        self.entities.remove(&object.entity);
+        remove_id(&mut self.by_name, &object.name, id);
+        remove_id(&mut self.by_class, &object.class, id);
+        remove_id(&mut self.by_layer, &object.layer_idx, id);
+        Some(object.entity)
+    }
+
+    /// Removes the object by its entity, returns its Tiled object id.
+    pub fn remove_entity(&mut self, entity: Entity) -> Option<u32> {
+        let id = self.get_id(entity)?;
+        self.remove(id);
+        Some(id)
+    }
+
+    /// Returns an iterator with all object ids and entities, not ordered.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, Entity)> + '_ {
+        self.objects.iter().map(|(id, object)| (*id, object.entity))
+    }
+
+    /// Number of stored objects.
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Clear all entities from storage.
+    pub fn clear(&mut self) {
+        self.objects.clear();
+        self.entities.clear();
+        self.by_name.clear();
+        self.by_class.clear();
+        self.by_layer.clear();
+    }
+
+    fn entities_of<'a>(
+        &'a self,
+        ids: Option<&'a Vec<u32>>,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        ids.into_iter().flatten().filter_map(|id| self.get(*id))
     }
 }
 
@@ -177,6 +655,9 @@ impl Display for TileStorageError {
             TileStorageError::NoLayerWithIndex => {
                 f.write_str("No layer with that index!")?
             }
+            TileStorageError::NoLayerWithName => {
+                f.write_str("No layer with that name!")?
+            }
             TileStorageError::TileCellEmpty => {
                 f.write_str("Tile cell is empty!")?
             }
@@ -190,6 +671,20 @@ impl Display for TileStorageError {
 
 impl Error for TileStorageError {}
 
+// ───── Utility functions ────────────────────────────────────────────────── //
+
+fn remove_id<K>(index: &mut HashMap<K, Vec<u32>>, key: &K, id: u32)
+where
+    K: std::hash::Hash + Eq,
+{
+    if let Some(ids) = index.get_mut(key) {
+        ids.retain(|stored| *stored != id);
+        if ids.is_empty() {
+            index.remove(key);
+        }
+    }
+}
+
 // ───── Unit tests ───────────────────────────────────────────────────────── //
 
 mod tests {
@@ -211,10 +706,9 @@ mod tests {
                 ),
             );
         }
-        TileStorage {
-            tiles,
-            bevy_ecs_tilemap_tile_storages: HashMap::new(),
-        }
+        let mut names = HashMap::new();
+        names.insert("first".to_string(), 1);
+        TileStorage { tiles, names }
     }
 
     #[test]
@@ -293,4 +787,190 @@ mod tests {
 
         assert_eq!(tile_storage.iter_all().count(), 12);
     }
+
+    #[test]
+    fn test_layer_by_name() {
+        let tile_storage = initialize_tile_storage();
+        assert_eq!(tile_storage.layer_index("first"), Ok(1));
+        assert_eq!(
+            tile_storage.get("first", &TilePos::new(1, 0)),
+            Ok(Entity::from_raw(2))
+        );
+        assert_eq!(
+            tile_storage.get("second", &TilePos::new(1, 0)),
+            Err(TileStorageError::NoLayerWithName)
+        );
+        assert_eq!(
+            tile_storage.iter_layer_tiles("first").collect::<Vec<_>>(),
+            vec![
+                (TilePos::new(0, 0), Entity::from_raw(1)),
+                (TilePos::new(1, 0), Entity::from_raw(2)),
+                (TilePos::new(1, 1), Entity::from_raw(3)),
+            ]
+        );
+        assert_eq!(tile_storage.iter_all_tiles().count(), 9);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let tile_storage = initialize_tile_storage();
+        let neighbours: Vec<_> = tile_storage
+            .neighbours_4(1, &TilePos::new(0, 0))
+            .unwrap()
+            .collect();
+        assert_eq!(neighbours, vec![(TilePos::new(1, 0), Entity::from_raw(2))]);
+
+        let neighbours: Vec<_> = tile_storage
+            .neighbours_8(1, &TilePos::new(0, 0))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            neighbours,
+            vec![
+                (TilePos::new(1, 1), Entity::from_raw(3)),
+                (TilePos::new(1, 0), Entity::from_raw(2)),
+            ]
+        );
+
+        // Should be error
+        assert_eq!(
+            tile_storage.neighbours_4(1, &TilePos::new(2, 0)).err(),
+            Some(TileStorageError::TileOutOfLayer)
+        );
+        assert_eq!(
+            tile_storage.neighbours_8(4, &TilePos::new(0, 0)).err(),
+            Some(TileStorageError::NoLayerWithIndex)
+        );
+    }
+
+    #[test]
+    fn test_region() {
+        let tile_storage = initialize_tile_storage();
+        let region = tile_storage
+            .region(2, &TilePos::new(1, 1), &TilePos::new(0, 0))
+            .unwrap();
+        assert_eq!(
+            region.collect::<Vec<_>>(),
+            vec![
+                (TilePos::new(0, 0), Entity::from_raw(2)),
+                (TilePos::new(1, 0), Entity::from_raw(3)),
+                (TilePos::new(1, 1), Entity::from_raw(4)),
+            ]
+        );
+        let region = tile_storage
+            .region(2, &TilePos::new(1, 0), &TilePos::new(1, 0))
+            .unwrap();
+        assert_eq!(region.count(), 1);
+
+        // Should be error
+        assert_eq!(
+            tile_storage
+                .region(2, &TilePos::new(0, 0), &TilePos::new(2, 2))
+                .err(),
+            Some(TileStorageError::TileOutOfLayer)
+        );
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let tile_storage = initialize_tile_storage();
+        let filled = tile_storage
+            .flood_fill(1, &TilePos::new(0, 0), |_, _| true)
+            .unwrap();
+        assert_eq!(filled.len(), 3);
+
+        // The only path to (1, 1) is blocked
+        let filled = tile_storage
+            .flood_fill(1, &TilePos::new(0, 0), |_, entity| {
+                entity != Entity::from_raw(2)
+            })
+            .unwrap();
+        assert_eq!(filled, vec![(TilePos::new(0, 0), Entity::from_raw(1))]);
+
+        let filled = tile_storage
+            .flood_fill(1, &TilePos::new(0, 0), |_, _| false)
+            .unwrap();
+        assert!(filled.is_empty());
+
+        // Should be error
+        assert_eq!(
+            tile_storage.flood_fill(1, &TilePos::new(0, 1), |_, _| true),
+            Err(TileStorageError::TileCellEmpty)
+        );
+    }
+
+    #[test]
+    fn test_object_storage() {
+        let mut object_storage = ObjectStorage::new();
+        object_storage.set(1, Entity::from_raw(1), 0, "door", "Door");
+        object_storage.set(2, Entity::from_raw(2), 0, "door", "Door");
+        object_storage.set(3, Entity::from_raw(3), 1, "lever", "Lever");
+        assert_eq!(object_storage.get_by_name("door").count(), 2);
+        assert_eq!(object_storage.name_of(3), Some("lever"));
+        assert_eq!(object_storage.class_of(3), Some("Lever"));
+        assert_eq!(
+            object_storage.get_by_class("Lever").collect::<Vec<_>>(),
+            vec![Entity::from_raw(3)]
+        );
+        assert_eq!(
+            object_storage.iter_layer(0).collect::<Vec<_>>(),
+            vec![(1, Entity::from_raw(1)), (2, Entity::from_raw(2))]
+        );
+
+        // Replacing the object updates indices
+        object_storage.set(2, Entity::from_raw(4), 1, "gate", "Door");
+        assert_eq!(object_storage.get_by_name("door").count(), 1);
+        assert_eq!(object_storage.get_id(Entity::from_raw(2)), None);
+        assert_eq!(object_storage.iter_layer(1).count(), 2);
+
+        assert_eq!(object_storage.remove_entity(Entity::from_raw(1)), Some(1));
+        assert_eq!(object_storage.get(1), None);
+        assert_eq!(object_storage.get_by_name("door").count(), 0);
+        assert_eq!(object_storage.len(), 2);
+    }
+
+    #[test]
+    fn test_object_storage_move_entity() {
+        let mut object_storage = ObjectStorage::new();
+        object_storage.set(1, Entity::from_raw(1), 0, "door", "Door");
+        object_storage.set(2, Entity::from_raw(1), 1, "gate", "Gate");
+        assert_eq!(object_storage.get(1), None);
+        assert_eq!(object_storage.get(2), Some(Entity::from_raw(1)));
+        assert_eq!(object_storage.get_id(Entity::from_raw(1)), Some(2));
+        assert_eq!(object_storage.get_by_name("door").count(), 0);
+        assert_eq!(object_storage.get_by_class("Door").count(), 0);
+        assert_eq!(object_storage.iter_layer(0).count(), 0);
+        assert_eq!(
+            object_storage.get_by_class("Gate").collect::<Vec<_>>(),
+            vec![Entity::from_raw(1)]
+        );
+        assert_eq!(object_storage.len(), 1);
+        assert!(object_storage.by_name.get("door").is_none());
+
+        // Setting the same entity under the same id keeps a single entry
+        object_storage.set(2, Entity::from_raw(1), 1, "gate", "Gate");
+        assert_eq!(object_storage.get_by_name("gate").count(), 1);
+        assert_eq!(object_storage.len(), 1);
+    }
+
+    #[test]
+    fn test_layer_storage() {
+        let mut layer_storage = LayerStorage::new();
+        layer_storage.set(0, Entity::from_raw(1), 5, "Ground", None);
+        layer_storage.set(1, Entity::from_raw(2), 7, "Water", Some("Water"));
+        assert_eq!(layer_storage.get(1), Some(Entity::from_raw(2)));
+        assert_eq!(layer_storage.id_of(1), Some(7));
+        assert_eq!(layer_storage.name_of(0), Some("Ground"));
+        assert_eq!(layer_storage.class_of(0), None);
+        assert_eq!(layer_storage.class_of(1), Some("Water"));
+        assert_eq!(
+            layer_storage.layers.get(&Name::new("Water")),
+            Some(&Entity::from_raw(2))
+        );
+        assert_eq!(layer_storage.iter().count(), 2);
+
+        layer_storage.clear();
+        assert_eq!(layer_storage.get(0), None);
+        assert!(layer_storage.layers.is_empty());
+    }
 }
@@ -0,0 +1,208 @@
+//! This module contains `TileOffset` type and grid directions.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use bevy::prelude::*;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// A signed offset between tile positions, Y up like [TilePos](super::TilePos).
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// let map_size = UVec2::new(10, 10);
+/// let step = TileOffset::from(CardinalDirection::West);
+/// assert_eq!(TilePos::new(0, 3).checked_add(step, map_size), None);
+/// assert_eq!(
+///     TilePos::new(0, 3).saturating_add(step * 2, map_size),
+///     TilePos::new(0, 3)
+/// );
+/// assert_eq!(
+///     TilePos::new(4, 3).checked_add(IVec2::new(-2, 1), map_size),
+///     Some(TilePos::new(2, 4))
+/// );
+/// ```
+#[derive(Default, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct TileOffset {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl TileOffset {
+    pub const ZERO: TileOffset = TileOffset::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<IVec2> for TileOffset {
+    fn from(v: IVec2) -> Self {
+        Self { x: v.x, y: v.y }
+    }
+}
+
+impl From<TileOffset> for IVec2 {
+    fn from(offset: TileOffset) -> Self {
+        IVec2::new(offset.x, offset.y)
+    }
+}
+
+impl Add for TileOffset {
+    type Output = TileOffset;
+
+    fn add(self, rhs: TileOffset) -> Self::Output {
+        TileOffset::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for TileOffset {
+    type Output = TileOffset;
+
+    fn sub(self, rhs: TileOffset) -> Self::Output {
+        TileOffset::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for TileOffset {
+    type Output = TileOffset;
+
+    fn neg(self) -> Self::Output {
+        TileOffset::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i32> for TileOffset {
+    type Output = TileOffset;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        TileOffset::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/// Orthogonal directions on the grid, North is Y up.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum CardinalDirection {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl CardinalDirection {
+    /// All directions clockwise from North.
+    pub const ALL: [CardinalDirection; 4] = [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ];
+
+    /// Offset to the neighbour tile in this direction.
+    pub const fn offset(self) -> TileOffset {
+        match self {
+            CardinalDirection::North => TileOffset::new(0, 1),
+            CardinalDirection::East => TileOffset::new(1, 0),
+            CardinalDirection::South => TileOffset::new(0, -1),
+            CardinalDirection::West => TileOffset::new(-1, 0),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            CardinalDirection::North => CardinalDirection::South,
+            CardinalDirection::East => CardinalDirection::West,
+            CardinalDirection::South => CardinalDirection::North,
+            CardinalDirection::West => CardinalDirection::East,
+        }
+    }
+}
+
+/// Orthogonal and diagonal directions on the grid, North is Y up.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum OrdinalDirection {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl OrdinalDirection {
+    /// All directions clockwise from North.
+    pub const ALL: [OrdinalDirection; 8] = [
+        OrdinalDirection::North,
+        OrdinalDirection::NorthEast,
+        OrdinalDirection::East,
+        OrdinalDirection::SouthEast,
+        OrdinalDirection::South,
+        OrdinalDirection::SouthWest,
+        OrdinalDirection::West,
+        OrdinalDirection::NorthWest,
+    ];
+
+    /// Offset to the neighbour tile in this direction.
+    pub const fn offset(self) -> TileOffset {
+        match self {
+            OrdinalDirection::North => TileOffset::new(0, 1),
+            OrdinalDirection::NorthEast => TileOffset::new(1, 1),
+            OrdinalDirection::East => TileOffset::new(1, 0),
+            OrdinalDirection::SouthEast => TileOffset::new(1, -1),
+            OrdinalDirection::South => TileOffset::new(0, -1),
+            OrdinalDirection::SouthWest => TileOffset::new(-1, -1),
+            OrdinalDirection::West => TileOffset::new(-1, 0),
+            OrdinalDirection::NorthWest => TileOffset::new(-1, 1),
+        }
+    }
+
+    pub const fn opposite(self) -> Self {
+        match self {
+            OrdinalDirection::North => OrdinalDirection::South,
+            OrdinalDirection::NorthEast => OrdinalDirection::SouthWest,
+            OrdinalDirection::East => OrdinalDirection::West,
+            OrdinalDirection::SouthEast => OrdinalDirection::NorthWest,
+            OrdinalDirection::South => OrdinalDirection::North,
+            OrdinalDirection::SouthWest => OrdinalDirection::NorthEast,
+            OrdinalDirection::West => OrdinalDirection::East,
+            OrdinalDirection::NorthWest => OrdinalDirection::SouthEast,
+        }
+    }
+
+    /// Returns `true` for North, East, South and West.
+    pub const fn is_cardinal(self) -> bool {
+        matches!(
+            self,
+            OrdinalDirection::North
+                | OrdinalDirection::East
+                | OrdinalDirection::South
+                | OrdinalDirection::West
+        )
+    }
+}
+
+impl From<CardinalDirection> for OrdinalDirection {
+    fn from(direction: CardinalDirection) -> Self {
+        match direction {
+            CardinalDirection::North => OrdinalDirection::North,
+            CardinalDirection::East => OrdinalDirection::East,
+            CardinalDirection::South => OrdinalDirection::South,
+            CardinalDirection::West => OrdinalDirection::West,
+        }
+    }
+}
+
+impl From<CardinalDirection> for TileOffset {
+    fn from(direction: CardinalDirection) -> Self {
+        direction.offset()
+    }
+}
+
+impl From<OrdinalDirection> for TileOffset {
+    fn from(direction: OrdinalDirection) -> Self {
+        direction.offset()
+    }
+}
@@ -2,9 +2,35 @@
 
 use bevy::prelude::*;
 
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use super::tile_offset::TileOffset;
+
 // ───── Body ─────────────────────────────────────────────────────────────── //
 
 /// A tile position in the tilemap grid.
+///
+/// The origin is the bottom-left tile and Y points up, like in Bevy world
+/// coordinates and `bevy_ecs_tilemap`, so tile layers spawned by any renderer
+/// use the same positions. Tiled counts rows from the top, use
+/// [TilePos::from_tiled] and [TilePos::to_tiled] to convert positions. Every
+/// tile entity has its `TilePos` component.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// let map_size = UVec2::new(10, 8);
+/// let tile_size = Vec2::new(16., 16.);
+///
+/// // Top-left tile in Tiled.
+/// let tile_pos = TilePos::from_tiled(UVec2::new(0, 0), map_size);
+/// assert_eq!(tile_pos, TilePos::new(0, 7));
+/// assert_eq!(tile_pos.to_world(tile_size), Vec2::new(8., 120.));
+/// assert_eq!(
+///     TilePos::from_world(Vec2::new(8., 120.), map_size, tile_size),
+///     Some(tile_pos)
+/// );
+/// ```
 #[derive(
     Component,
     Reflect,
@@ -34,12 +60,210 @@ impl TilePos {
         ((self.y * tilemap_size.x) + self.x) as usize
     }
 
+    /// Converts an index in a flattened vector (1D) back into a tile position
+    /// (2D) in a tilemap of the specified size.
+    pub fn from_index(index: usize, tilemap_size: UVec2) -> Self {
+        let index = index as u32;
+        Self {
+            x: index % tilemap_size.x,
+            y: index / tilemap_size.x,
+        }
+    }
+
+    /// Converts a position in Tiled coordinates (top-left origin, Y down).
+    pub fn from_tiled(tiled_pos: UVec2, map_size: UVec2) -> Self {
+        Self {
+            x: tiled_pos.x,
+            y: map_size.y.saturating_sub(tiled_pos.y + 1),
+        }
+    }
+
+    /// Converts the position into Tiled coordinates (top-left origin, Y down).
+    pub fn to_tiled(&self, map_size: UVec2) -> UVec2 {
+        UVec2::new(self.x, map_size.y.saturating_sub(self.y + 1))
+    }
+
+    /// Returns the center of the tile relative to the bottom-left corner of
+    /// its layer, in world units.
+    pub fn to_world(&self, tile_size: Vec2) -> Vec2 {
+        (Vec2::from(self) + 0.5) * tile_size
+    }
+
+    /// Returns the tile under the point relative to the bottom-left corner of
+    /// its layer, or `None` if the point is outside of the map.
+    pub fn from_world(
+        pos: Vec2,
+        map_size: UVec2,
+        tile_size: Vec2,
+    ) -> Option<Self> {
+        let pos = (pos / tile_size).floor();
+        if pos.x < 0. || pos.y < 0. {
+            return None;
+        }
+        let tile_pos = Self::new(pos.x as u32, pos.y as u32);
+        tile_pos.within_map_bounds(map_size).then_some(tile_pos)
+    }
+
+    /// Adds the offset, returns `None` if the result lies outside of a
+    /// tilemap of the specified size.
+    pub fn checked_add(
+        &self,
+        offset: impl Into<TileOffset>,
+        map_size: UVec2,
+    ) -> Option<Self> {
+        let offset = offset.into();
+        let x = i64::from(self.x) + i64::from(offset.x);
+        let y = i64::from(self.y) + i64::from(offset.y);
+        let in_bounds = |v: i64, size: u32| v >= 0 && v < i64::from(size);
+        (in_bounds(x, map_size.x) && in_bounds(y, map_size.y))
+            .then(|| Self::new(x as u32, y as u32))
+    }
+
+    /// Adds the offset, clamping the result to a tilemap of the specified
+    /// size.
+    pub fn saturating_add(
+        &self,
+        offset: impl Into<TileOffset>,
+        map_size: UVec2,
+    ) -> Self {
+        let offset = offset.into();
+        let clamp = |v: u32, offset: i32, size: u32| {
+            let max = i64::from(size.saturating_sub(1));
+            (i64::from(v) + i64::from(offset)).clamp(0, max) as u32
+        };
+        Self::new(
+            clamp(self.x, offset.x, map_size.x),
+            clamp(self.y, offset.y, map_size.y),
+        )
+    }
+
+    /// Returns the offset from `self` to `other`, clamped to the `i32`
+    /// range.
+    pub fn offset_to(&self, other: &TilePos) -> TileOffset {
+        let offset = |from: u32, to: u32| {
+            (i64::from(to) - i64::from(from))
+                .clamp(i64::from(i32::MIN), i64::from(i32::MAX))
+                as i32
+        };
+        TileOffset::new(offset(self.x, other.x), offset(self.y, other.y))
+    }
+
+    /// Number of orthogonal steps between the tiles.
+    pub fn manhattan_distance(&self, other: &TilePos) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Number of steps between the tiles, when diagonal steps are allowed.
+    pub fn chebyshev_distance(&self, other: &TilePos) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Returns tiles of the straight line from `self` to `other`, both ends
+    /// included.
+    pub fn line_to(&self, other: &TilePos) -> TileLine {
+        TileLine::new(*self, *other)
+    }
+
+    /// Returns tiles at the `radius` Chebyshev distance, the border of a
+    /// square around `self`, clockwise from its top-left corner. Tiles outside
+    /// of a tilemap of the specified size are skipped.
+    pub fn ring(
+        &self,
+        radius: u32,
+        map_size: UVec2,
+    ) -> impl Iterator<Item = TilePos> {
+        let (x, y, r) =
+            (i64::from(self.x), i64::from(self.y), i64::from(radius));
+        let (width, height) = (i64::from(map_size.x), i64::from(map_size.y));
+        // Tiles of the side clipped to the map, empty if the side is outside.
+        let side = |fixed: i64, fixed_size: i64, from: i64, to: i64, size| {
+            if fixed < 0 || fixed >= fixed_size {
+                1..=0
+            } else {
+                from.max(0)..=to.min(size - 1)
+            }
+        };
+        let top = side(y + r, height, x - r, x + r, width)
+            .map(move |tile_x| (tile_x, y + r));
+        let right = side(x + r, width, y - r, y + r - 1, height)
+            .rev()
+            .map(move |tile_y| (x + r, tile_y));
+        let bottom = side(y - r, height, x - r, x + r - 1, width)
+            .rev()
+            .map(move |tile_x| (tile_x, y - r));
+        let left = side(x - r, width, y - r + 1, y + r - 1, height)
+            .map(move |tile_y| (x - r, tile_y));
+        top.chain(right)
+            .chain(bottom)
+            .chain(left)
+            .map(|(x, y)| TilePos::new(x as u32, y as u32))
+    }
+
     /// Checks to see if `self` lies within a tilemap of the specified size.
     pub fn within_map_bounds(&self, map_size: UVec2) -> bool {
         self.x < map_size.x && self.y < map_size.y
     }
 }
 
+/// Iterator over tiles of a straight line, see [TilePos::line_to].
+#[derive(Clone, Debug)]
+pub struct TileLine {
+    // `i64`, so any `u32` positions and distances between them fit.
+    current: [i64; 2],
+    end: [i64; 2],
+    delta: [i64; 2],
+    step: [i64; 2],
+    error: i64,
+    finished: bool,
+}
+
+impl TileLine {
+    fn new(start: TilePos, end: TilePos) -> Self {
+        let current = [i64::from(start.x), i64::from(start.y)];
+        let end = [i64::from(end.x), i64::from(end.y)];
+        let delta = [(end[0] - current[0]).abs(), -(end[1] - current[1]).abs()];
+        TileLine {
+            current,
+            end,
+            delta,
+            step: [
+                (end[0] - current[0]).signum(),
+                (end[1] - current[1]).signum(),
+            ],
+            error: delta[0] + delta[1],
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for TileLine {
+    type Item = TilePos;
+
+    // Bresenham's line algorithm
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        // Stays between `u32` ends of the line.
+        let tile_pos =
+            TilePos::new(self.current[0] as u32, self.current[1] as u32);
+        if self.current == self.end {
+            self.finished = true;
+            return Some(tile_pos);
+        }
+        let doubled = 2 * self.error;
+        if doubled >= self.delta[1] {
+            self.error += self.delta[1];
+            self.current[0] += self.step[0];
+        }
+        if doubled <= self.delta[0] {
+            self.error += self.delta[0];
+            self.current[1] += self.step[1];
+        }
+        Some(tile_pos)
+    }
+}
+
 impl From<TilePos> for UVec2 {
     fn from(pos: TilePos) -> Self {
         UVec2::new(pos.x, pos.y)
@@ -69,3 +293,163 @@ impl From<&TilePos> for Vec2 {
         Vec2::new(pos.x as f32, pos.y as f32)
     }
 }
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{CardinalDirection, OrdinalDirection};
+
+    #[test]
+    fn test_tiled_conversion() {
+        let map_size = UVec2::new(4, 3);
+        let tile_pos = TilePos::from_tiled(UVec2::new(1, 0), map_size);
+        assert_eq!(tile_pos, TilePos::new(1, 2));
+        assert_eq!(tile_pos.to_tiled(map_size), UVec2::new(1, 0));
+        assert_eq!(
+            TilePos::from_tiled(UVec2::new(3, 2), map_size),
+            TilePos::new(3, 0)
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        let map_size = UVec2::new(4, 3);
+        let tile_pos = TilePos::new(0, 2);
+        assert_eq!(
+            tile_pos.checked_add(CardinalDirection::West, map_size),
+            None
+        );
+        assert_eq!(
+            tile_pos.checked_add(CardinalDirection::North, map_size),
+            None
+        );
+        assert_eq!(
+            tile_pos.checked_add(OrdinalDirection::SouthEast, map_size),
+            Some(TilePos::new(1, 1))
+        );
+        assert_eq!(
+            tile_pos.saturating_add(TileOffset::new(-5, -5), map_size),
+            TilePos::new(0, 0)
+        );
+        assert_eq!(
+            tile_pos.saturating_add(IVec2::new(9, 1), map_size),
+            TilePos::new(3, 2)
+        );
+        assert_eq!(
+            tile_pos.offset_to(&TilePos::new(3, 0)),
+            TileOffset::new(3, -2)
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = TilePos::new(1, 4);
+        let b = TilePos::new(4, 2);
+        assert_eq!(a.manhattan_distance(&b), 5);
+        assert_eq!(a.chebyshev_distance(&b), 3);
+        assert_eq!(a.chebyshev_distance(&a), 0);
+    }
+
+    #[test]
+    fn test_line() {
+        let line: Vec<_> =
+            TilePos::new(0, 0).line_to(&TilePos::new(3, 1)).collect();
+        assert_eq!(
+            line,
+            vec![
+                TilePos::new(0, 0),
+                TilePos::new(1, 0),
+                TilePos::new(2, 1),
+                TilePos::new(3, 1),
+            ]
+        );
+        let line: Vec<_> =
+            TilePos::new(2, 3).line_to(&TilePos::new(2, 0)).collect();
+        assert_eq!(line.len(), 4);
+        assert_eq!(line.last(), Some(&TilePos::new(2, 0)));
+        assert_eq!(TilePos::new(1, 1).line_to(&TilePos::new(1, 1)).count(), 1);
+    }
+
+    #[test]
+    fn test_line_large_coordinates() {
+        let line: Vec<_> = TilePos::new(0, 0)
+            .line_to(&TilePos::new(u32::MAX, 1))
+            .take(3)
+            .collect();
+        assert_eq!(
+            line,
+            vec![TilePos::new(0, 0), TilePos::new(1, 0), TilePos::new(2, 0)]
+        );
+        let line: Vec<_> = TilePos::new(u32::MAX, u32::MAX)
+            .line_to(&TilePos::new(u32::MAX - 2, u32::MAX))
+            .collect();
+        assert_eq!(
+            line,
+            vec![
+                TilePos::new(u32::MAX, u32::MAX),
+                TilePos::new(u32::MAX - 1, u32::MAX),
+                TilePos::new(u32::MAX - 2, u32::MAX),
+            ]
+        );
+        let offset = TilePos::new(0, 0).offset_to(&TilePos::new(u32::MAX, 1));
+        assert_eq!(offset, TileOffset::new(i32::MAX, 1));
+    }
+
+    #[test]
+    fn test_ring() {
+        let map_size = UVec2::new(10, 10);
+        assert_eq!(TilePos::new(5, 5).ring(0, map_size).count(), 1);
+        assert_eq!(TilePos::new(5, 5).ring(2, map_size).count(), 16);
+        // Only the top and right sides are inside of the map
+        let ring: Vec<_> = TilePos::new(0, 0).ring(1, map_size).collect();
+        assert_eq!(
+            ring,
+            vec![TilePos::new(0, 1), TilePos::new(1, 1), TilePos::new(1, 0),]
+        );
+    }
+
+    #[test]
+    fn test_ring_large_radius() {
+        // Radius above `i32::MAX` is outside of the map on every side
+        let map_size = UVec2::new(10, 10);
+        assert_eq!(TilePos::new(5, 5).ring(3_000_000_000, map_size).count(), 0);
+        assert_eq!(TilePos::new(5, 5).ring(u32::MAX, map_size).count(), 0);
+
+        // Coordinates above `i32::MAX`
+        let map_size = UVec2::new(u32::MAX, 10);
+        let ring: Vec<_> =
+            TilePos::new(u32::MAX - 1, 5).ring(1, map_size).collect();
+        assert_eq!(
+            ring,
+            vec![
+                TilePos::new(u32::MAX - 2, 6),
+                TilePos::new(u32::MAX - 1, 6),
+                TilePos::new(u32::MAX - 1, 4),
+                TilePos::new(u32::MAX - 2, 4),
+                TilePos::new(u32::MAX - 2, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_world_conversion() {
+        let map_size = UVec2::new(4, 3);
+        let tile_size = Vec2::new(16., 8.);
+        let tile_pos = TilePos::new(2, 1);
+        assert_eq!(tile_pos.to_world(tile_size), Vec2::new(40., 12.));
+        assert_eq!(
+            TilePos::from_world(Vec2::new(47.9, 8.), map_size, tile_size),
+            Some(tile_pos)
+        );
+        assert_eq!(
+            TilePos::from_world(Vec2::new(-1., 8.), map_size, tile_size),
+            None
+        );
+        assert_eq!(
+            TilePos::from_world(Vec2::new(64., 8.), map_size, tile_size),
+            None
+        );
+    }
+}
@@ -0,0 +1,283 @@
+//! This module contains `TiledMapError` event type.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+use crate::components::TileStorageError;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Points to the element of the map which caused an error.
+#[derive(Default, Debug, Clone)]
+pub struct TiledErrorContext {
+    /// Weak handle of the map.
+    pub map: Handle<TiledMapAsset>,
+    /// Index and name of the layer.
+    pub layer: Option<(usize, String)>,
+    /// Tiled id of the object.
+    pub object_id: Option<u32>,
+    /// Tileset index and tile id.
+    pub tile: Option<(usize, tiled::TileId)>,
+}
+
+impl TiledErrorContext {
+    /// Create new context for the map.
+    pub fn new(map: &Handle<TiledMapAsset>) -> Self {
+        TiledErrorContext {
+            map: map.clone_weak(),
+            ..default()
+        }
+    }
+
+    /// Returns a copy of the context pointing to the layer.
+    pub fn with_layer(&self, layer_idx: usize, layer_name: &str) -> Self {
+        TiledErrorContext {
+            layer: Some((layer_idx, layer_name.to_string())),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the context pointing to the object.
+    pub fn with_object(&self, object_id: u32) -> Self {
+        TiledErrorContext {
+            object_id: Some(object_id),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of the context pointing to the tile.
+    pub fn with_tile(
+        &self,
+        tileset_idx: usize,
+        tile_id: tiled::TileId,
+    ) -> Self {
+        TiledErrorContext {
+            tile: Some((tileset_idx, tile_id)),
+            ..self.clone()
+        }
+    }
+}
+
+impl Display for TiledErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "map {:?}", self.map.id())?;
+        if let Some((idx, ref name)) = self.layer {
+            write!(f, ", layer {} \"{}\"", idx, name)?;
+        }
+        if let Some(id) = self.object_id {
+            write!(f, ", object {}", id)?;
+        }
+        if let Some((tls_idx, tile_id)) = self.tile {
+            write!(f, ", tileset {} tile {}", tls_idx, tile_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors which can occur when spawning a Tiled map. The offending element is
+/// skipped, and the error is sent as an event instead of panicking.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_report_map_errors(mut errors: EventReader<TiledMapError>) {
+///     for error in errors.iter() {
+///         warn!("Broken map: {}", error);
+///     }
+/// }
+/// ```
+#[derive(Event, Debug, Clone)]
+pub enum TiledMapError {
+    /// Map file or its images can't be loaded.
+    LoadFailed { context: TiledErrorContext },
+    /// Only orthogonal maps are supported.
+    UnsupportedOrientation {
+        context: TiledErrorContext,
+        orientation: tiled::Orientation,
+    },
+    /// Infinite tile layers are not supported.
+    InfiniteLayer { context: TiledErrorContext },
+    /// Image and group layers are not supported, object layers are not
+    /// supported by `bevy_ecs_tilemap` renderer.
+    UnsupportedLayerType {
+        context: TiledErrorContext,
+        layer_type: &'static str,
+    },
+    /// This collision shape can't be turned into a collider.
+    UnsupportedCollisionShape {
+        context: TiledErrorContext,
+        shape: String,
+    },
+    /// Collider can't be built from the collision shape points.
+    InvalidCollider { context: TiledErrorContext },
+    /// Tileset images were not loaded.
+    MissingTilesetTexture {
+        context: TiledErrorContext,
+        tileset_idx: usize,
+    },
+    /// Texture atlas can't be built for the tileset.
+    AtlasBuildFailed {
+        context: TiledErrorContext,
+        tileset_idx: usize,
+        reason: String,
+    },
+    /// There are no atlas for the tileset.
+    MissingAtlas {
+        context: TiledErrorContext,
+        tileset_idx: usize,
+    },
+    /// Tilesets from templates are not supported.
+    TemplateTileset { context: TiledErrorContext },
+    /// Tile can't be stored in the `TileStorage`.
+    TileStorage {
+        context: TiledErrorContext,
+        error: TileStorageError,
+    },
+    /// Property type doesn't match the field of a reflected component.
+    ReflectTypeMismatch {
+        context: TiledErrorContext,
+        /// Tiled class of the component.
+        component: String,
+        /// Dotted path to the field.
+        field: String,
+        /// Type name of the field.
+        expected: String,
+        /// Type of the property.
+        found: &'static str,
+    },
+    /// `TiledComponentFactory` returned an error.
+    ComponentFailed {
+        context: TiledErrorContext,
+        /// Tiled class of the component.
+        class: String,
+        reason: String,
+    },
+    /// `pixels_per_unit` setting or layer property is not a finite positive
+    /// number, the default one is used.
+    InvalidPixelsPerUnit {
+        context: TiledErrorContext,
+        value: f32,
+    },
+    /// `renderer` layer property is not a string, or the renderer with this
+    /// name is not registered. The layer is spawned with sprites then.
+    UnknownRenderer {
+        context: TiledErrorContext,
+        renderer: String,
+    },
+    /// Object property references an object, which is not spawned.
+    MissingObjectRef {
+        context: TiledErrorContext,
+        /// Dotted path to the property.
+        property: String,
+        /// Tiled id of the referenced object.
+        target: u32,
+    },
+}
+
+impl TiledMapError {
+    /// Returns the location of the error.
+    pub fn context(&self) -> &TiledErrorContext {
+        match self {
+            TiledMapError::LoadFailed { context }
+            | TiledMapError::UnsupportedOrientation { context, .. }
+            | TiledMapError::InfiniteLayer { context }
+            | TiledMapError::UnsupportedLayerType { context, .. }
+            | TiledMapError::UnsupportedCollisionShape { context, .. }
+            | TiledMapError::InvalidCollider { context }
+            | TiledMapError::MissingTilesetTexture { context, .. }
+            | TiledMapError::AtlasBuildFailed { context, .. }
+            | TiledMapError::MissingAtlas { context, .. }
+            | TiledMapError::TemplateTileset { context }
+            | TiledMapError::TileStorage { context, .. }
+            | TiledMapError::ReflectTypeMismatch { context, .. }
+            | TiledMapError::ComponentFailed { context, .. }
+            | TiledMapError::InvalidPixelsPerUnit { context, .. }
+            | TiledMapError::UnknownRenderer { context, .. }
+            | TiledMapError::MissingObjectRef { context, .. } => context,
+        }
+    }
+
+    /// Returns the weak handle of the map which caused the error.
+    pub fn map(&self) -> &Handle<TiledMapAsset> {
+        &self.context().map
+    }
+}
+
+impl Display for TiledMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TiledMapError::LoadFailed { .. } => {
+                f.write_str("Map or its images can't be loaded")?
+            }
+            TiledMapError::UnsupportedOrientation { orientation, .. } => {
+                write!(f, "Orientation {:?} is not supported", orientation)?
+            }
+            TiledMapError::InfiniteLayer { .. } => {
+                f.write_str("Infinite layers are not supported")?
+            }
+            TiledMapError::UnsupportedLayerType { layer_type, .. } => {
+                write!(f, "{} layers are not supported", layer_type)?
+            }
+            TiledMapError::UnsupportedCollisionShape { shape, .. } => {
+                write!(f, "Collision shape {} is not supported", shape)?
+            }
+            TiledMapError::InvalidCollider { .. } => {
+                f.write_str("Can't build collider from collision shape")?
+            }
+            TiledMapError::MissingTilesetTexture { tileset_idx, .. } => {
+                write!(f, "Images of tileset {} were not loaded", tileset_idx)?
+            }
+            TiledMapError::AtlasBuildFailed {
+                tileset_idx,
+                reason,
+                ..
+            } => write!(
+                f,
+                "Can't build atlas for tileset {}: {}",
+                tileset_idx, reason
+            )?,
+            TiledMapError::MissingAtlas { tileset_idx, .. } => {
+                write!(f, "There are no atlas for tileset {}", tileset_idx)?
+            }
+            TiledMapError::TemplateTileset { .. } => {
+                f.write_str("Tilesets from templates are not supported")?
+            }
+            TiledMapError::TileStorage { error, .. } => write!(f, "{}", error)?,
+            TiledMapError::ReflectTypeMismatch {
+                component,
+                field,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "Can't set {}.{} of type {} from {} property",
+                component, field, expected, found
+            )?,
+            TiledMapError::ComponentFailed { class, reason, .. } => {
+                write!(f, "Can't insert {}: {}", class, reason)?
+            }
+            TiledMapError::InvalidPixelsPerUnit { value, .. } => {
+                write!(f, "pixels_per_unit {} is not a positive number", value)?
+            }
+            TiledMapError::UnknownRenderer { renderer, .. } => {
+                write!(f, "Renderer {} is not registered", renderer)?
+            }
+            TiledMapError::MissingObjectRef {
+                property, target, ..
+            } => write!(
+                f,
+                "Object {} referenced by property {} is not spawned",
+                target, property
+            )?,
+        }
+        write!(f, " ({})", self.context())
+    }
+}
+
+impl Error for TiledMapError {}
@@ -0,0 +1,117 @@
+//! Events about tilemap lifecycle and the `SystemSet` for ordering against
+//! them.
+
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Systems of this crate are grouped into these sets, which are chained in the
+/// declaration order. Order your systems after [TiledMapSet::Spawn] to see
+/// spawned entities in the same frame, when spawn events are sent.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_setup_camera(mut events: EventReader<TiledMapSpawned>) {
+///     for event in events.iter() {
+///         info!("Map {:?} is ready", event.map);
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_systems(Update, system_setup_camera.after(TiledMapSet::Spawn));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum TiledMapSet {
+    /// Despawning maps and tearing down reloaded maps.
+    Despawn,
+    /// Waiting for assets and building atlases.
+    Load,
+    /// Spawning layers, tiles and objects. Commands are applied at the end of
+    /// this set.
+    Spawn,
+    /// Playing Tiled animations.
+    Animate,
+}
+
+/// Sent when all layers of the map are spawned.
+#[derive(Event, Debug, Clone)]
+pub struct TiledMapSpawned {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+}
+
+/// Sent when the layer with all its tiles or objects is spawned.
+#[derive(Event, Debug, Clone)]
+pub struct TiledLayerSpawned {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+    /// Spawned layer entity.
+    pub layer: Entity,
+    /// Layer id from Tiled.
+    pub id: u32,
+    pub name: String,
+    pub class: Option<String>,
+}
+
+/// Sent when the object from an object layer is spawned.
+#[derive(Event, Debug, Clone)]
+pub struct TiledObjectSpawned {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+    /// Parent layer entity.
+    pub layer: Entity,
+    /// Spawned object entity.
+    pub object: Entity,
+    /// Object id from Tiled.
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+}
+
+/// Sent when the map is despawned with [DespawnTiledMap](crate::components::DespawnTiledMap),
+/// or when its layers are torn down because of the asset reloading.
+/// [TiledLayerDespawned] and [TiledObjectDespawned] are sent before it.
+#[derive(Event, Debug, Clone)]
+pub struct TiledMapDespawned {
+    /// Entity with `Handle<TiledMapAsset>`, it doesn't exist anymore if the
+    /// map was despawned with `DespawnTiledMap`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+}
+
+/// Sent for every layer of the despawned or torn down map.
+#[derive(Event, Debug, Clone)]
+pub struct TiledLayerDespawned {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+    /// Despawned layer entity.
+    pub layer: Entity,
+    /// Layer id from Tiled.
+    pub id: u32,
+    pub name: String,
+    pub class: Option<String>,
+}
+
+/// Sent for every object of the despawned or torn down map, and for objects
+/// despawned separately.
+#[derive(Event, Debug, Clone)]
+pub struct TiledObjectDespawned {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    pub handle: Handle<TiledMapAsset>,
+    /// Despawned object entity.
+    pub object: Entity,
+    /// Object id from Tiled.
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+}
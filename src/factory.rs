@@ -0,0 +1,118 @@
+//! This module contains `TiledComponentFactory` trait, a fallible variant of
+//! `TiledComponent`, which knows where the element is placed in the map.
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+use crate::components::{TilePos, TiledProperties};
+use crate::error::TiledErrorContext;
+use crate::project::TiledProjectAsset;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Inserts components to tiles and objects with a specific class. Unlike
+/// [TiledComponent](crate::TiledComponent), it gets the location of the
+/// element and can fail, the error is sent as
+/// [TiledMapError::ComponentFailed](crate::error::TiledMapError::ComponentFailed).
+///
+/// It is implemented for closures and functions, register them with
+/// [TiledComponentReg](crate::prelude::TiledComponentReg)
+/// (`register_tiled_component_fn` infers argument types of a closure):
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::ecs::system::EntityCommands;
+/// use bevy_tiled_toolkit::prelude::*;
+/// use bevy_tiled_toolkit::factory::TiledComponentContext;
+///
+/// #[derive(Component)]
+/// struct Door {
+///     tile: TilePos,
+///     key: String,
+/// }
+///
+/// fn insert_door(
+///     commands: &mut EntityCommands,
+///     ctx: &TiledComponentContext,
+/// ) -> anyhow::Result<()> {
+///     let key = ctx.properties.get_string("lock.key")?;
+///     let tile = ctx.tile_pos.ok_or(anyhow::anyhow!("door is not a tile"))?;
+///     commands.insert(Door {
+///         tile,
+///         key: key.to_string(),
+///     });
+///     Ok(())
+/// }
+///
+/// let mut app = App::new();
+/// app.register_tiled_component_factory("Door", insert_door);
+/// ```
+pub trait TiledComponentFactory: Send + Sync + 'static {
+    fn insert_to_entity(
+        &self,
+        commands: &mut EntityCommands,
+        ctx: &TiledComponentContext,
+    ) -> anyhow::Result<()>;
+}
+
+impl<F> TiledComponentFactory for F
+where
+    F: Fn(&mut EntityCommands, &TiledComponentContext) -> anyhow::Result<()>
+        + Send
+        + Sync
+        + 'static,
+{
+    fn insert_to_entity(
+        &self,
+        commands: &mut EntityCommands,
+        ctx: &TiledComponentContext,
+    ) -> anyhow::Result<()> {
+        self(commands, ctx)
+    }
+}
+
+/// Tiled element, which components are inserted to.
+pub struct TiledComponentContext<'a> {
+    /// Class the factory is registered for.
+    pub class: &'a str,
+    /// Properties of the element, object properties override tile ones.
+    pub properties: &'a TiledProperties,
+    pub asset_server: &'a AssetServer,
+    /// Map, layer, object and tile of the element.
+    pub location: &'a TiledErrorContext,
+    /// Position of the tile in a tile layer, with the bottom-left origin.
+    pub tile_pos: Option<TilePos>,
+    /// Tileset of the tile or the tile object.
+    pub tileset: Option<&'a tiled::Tileset>,
+    /// Shape of the object.
+    pub object_shape: Option<&'a tiled::ObjectShape>,
+    /// Project from `TiledToolkitSettings::project`, to read enum values.
+    pub project: Option<&'a TiledProjectAsset>,
+}
+
+impl<'a> TiledComponentContext<'a> {
+    /// Weak handle of the map.
+    pub fn map(&self) -> &Handle<TiledMapAsset> {
+        &self.location.map
+    }
+
+    /// Index and name of the layer.
+    pub fn layer(&self) -> Option<(usize, &str)> {
+        self.location
+            .layer
+            .as_ref()
+            .map(|(idx, name)| (*idx, name.as_str()))
+    }
+
+    /// Tiled id of the object.
+    pub fn object_id(&self) -> Option<u32> {
+        self.location.object_id
+    }
+
+    /// Id of the tile in its tileset.
+    pub fn tile_id(&self) -> Option<tiled::TileId> {
+        self.location.tile.map(|(_, tile_id)| tile_id)
+    }
+}
@@ -6,7 +6,9 @@
 //! in your Bevy-game.
 //!
 //! Follow these steps to start:
-//! 1. Add the [TiledToolkitPlugin](self::plugin::TiledToolkitPlugin) to the [App].
+//! 1. Add the [TiledToolkitPlugin](self::plugin::TiledToolkitPlugin) to the
+//! [App], optionally configuring it with
+//! [TiledToolkitSettings](self::settings::TiledToolkitSettings).
 //! 2. Spawn a [TiledMapBundle](self::plugin::TiledMapBundle):
 //! ```
 //! use bevy::prelude::*;
@@ -79,11 +81,70 @@
 //! let mut app = App::new();
 //! app.register_tiled_component::<Ninja>();
 //! ```
-//! 3. In `Tiled`, in the `Class` field of your tile, insert the same name
-//! that `get_class_name` function returns.
-//! 4. Create properties in `Tiled` for your tile, and they will be passed to
-//! `insert_self_to_entity` function, where you can use them to initialize your
-//! component.
+//! 3. In `Tiled`, in the `Class` field of your tile or object, insert the same
+//! name that `get_class_name` function returns. Objects without tiles are
+//! supported too.
+//! 4. Create properties in `Tiled` for your tile or object, and they will be
+//! passed to `insert_self_to_entity` function, where you can use them to
+//! initialize your component. Properties of an object instance override
+//! properties of its tile.
+//!
+//! ### Deriving components
+//! `#[derive(TiledComponent)]` reads fields from properties with the same
+//! names, nested `#[derive(TiledClass)]` structs from class properties. Use
+//! `#[tiled(rename = "...")]`, `#[tiled(default)]` and
+//! `#[tiled(class = "...")]` to change the defaults:
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_tiled_toolkit::prelude::*;
+//!
+//! #[derive(TiledClass, Default)]
+//! struct Stats {
+//!     health: i32,
+//! }
+//!
+//! #[derive(Component, TiledComponent, Default)]
+//! #[tiled(class = "Ninja")]
+//! struct NinjaComponent {
+//!     strength: f32,
+//!     #[tiled(rename = "stats")]
+//!     ninja_stats: Stats,
+//!     #[tiled(default)]
+//!     portrait: Handle<Image>,
+//! }
+//!
+//! let mut app = App::new();
+//! app.register_tiled_component::<NinjaComponent>();
+//! ```
+//! Components which need the placement of the element can be spawned with a
+//! [TiledComponentFactory](self::factory::TiledComponentFactory), reflected
+//! components are inserted if their type name matches the class, and whole
+//! layers are handled with [TiledLayerHandler].
+//!
+//! ### Properties
+//! Map class and properties are inserted to the map entity as
+//! [TiledProperties](self::components::TiledProperties), with typed getters
+//! for dotted paths like `stats.health`. File properties are relative to the
+//! map. Maps, images (with `render` feature) and audio (with `audio` feature)
+//! are loaded as its dependencies.
+//!
+//! Classes, enums and member defaults from a `.tiled-project` file are used
+//! when `TiledToolkitSettings::project` is set, see
+//! [TiledProjectAsset](self::project::TiledProjectAsset). Rust types for them
+//! can be generated with the `bevy_tiled_toolkit_codegen` crate.
+//!
+//! ### Objects
+//! Spawned objects are stored in
+//! [ObjectStorage](self::components::ObjectStorage) of the map entity by
+//! their Tiled ids. Object properties are resolved into
+//! [TiledObjectRefs](self::components::TiledObjectRefs).
+//!
+//! ### Tile positions
+//! [TilePos](self::components::TilePos) uses the bottom-left origin with Y up
+//! and converts from and to Tiled and world coordinates.
+//! [TiledMapQuery](self::query::TiledMapQuery) finds tiles under a world
+//! position, and [TiledPickingPlugin](self::picking::TiledPickingPlugin)
+//! under the cursor.
 //!
 //! ### Rendering with bevy_ecs_tilemap
 //!
@@ -111,8 +172,44 @@
 //! only with [TextureAtlas]'es.
 //!
 //! To enable `bevy_ecs_tilemap` rendering on the particular layer, you should
-//! create boolean property named `bevy_ecs_tilemap` on the desired layer in
-//! Tiled, and click on checkbox of this property to activate it.
+//! create string property named `renderer` with `bevy_ecs_tilemap` value on
+//! the desired layer in Tiled (boolean `bevy_ecs_tilemap` property works too).
+//! To render all layers with `bevy_ecs_tilemap` by default, set
+//! `default_renderer` in [TiledToolkitSettings](self::settings::TiledToolkitSettings).
+//!
+//! ### Custom renderers
+//! Both built-in backends implement the
+//! [TiledLayerRenderer](self::renderer::TiledLayerRenderer) trait. Implement it
+//! to spawn layers your own way (e.g. a single mesh per layer), and register it
+//! with `app.register_layer_renderer("name", MyRenderer)`. The layer selects it
+//! with the `renderer` property set to `name`, or with the class `name`.
+//!
+//! ### Headless mode
+//! Disable the default `render` feature (`bevy_ecs_tilemap` feature requires
+//! it) to use maps without rendering, e.g. on a game server with
+//! `MinimalPlugins` and `AssetPlugin`. Tileset images are not loaded then, and
+//! tiles and objects are spawned only with [TilePos](self::components::TilePos),
+//! transforms, colliders and custom components:
+//! ```toml
+//! bevy_tiled_toolkit = { version = "0.1", default-features = false, features = ["rapier2d"] }
+//! ```
+//!
+//! ### Handling errors
+//! Malformed or unsupported map content never panics: the offending element
+//! is skipped and a [TiledMapError](self::error::TiledMapError) event is
+//! sent with the map handle and the layer/object/tile context.
+//!
+//! ### Lifecycle events
+//! [TiledMapSpawned](self::events::TiledMapSpawned),
+//! [TiledLayerSpawned](self::events::TiledLayerSpawned),
+//! [TiledObjectSpawned](self::events::TiledObjectSpawned) events are sent
+//! when the map is populated, and
+//! [TiledLayerDespawned](self::events::TiledLayerDespawned),
+//! [TiledObjectDespawned](self::events::TiledObjectDespawned) and
+//! [TiledMapDespawned](self::events::TiledMapDespawned) when it's torn down.
+//! Order your systems after
+//! [TiledMapSet::Spawn](self::events::TiledMapSet::Spawn) to access spawned
+//! entities in the same frame.
 //!
 //! ### Despawning tilemap
 //! Spawn a [DespawnTilemap](self::components::DespawnTiledMap) component to an
@@ -156,8 +253,19 @@ use bevy::{ecs::system::EntityCommands, utils::HashMap};
 mod app_extension;
 pub mod asset_loader;
 pub mod components;
+pub mod error;
+pub mod events;
+pub mod factory;
+#[cfg(feature = "render")]
+pub mod picking;
 mod plugin;
+pub mod project;
+pub mod property_value;
+pub mod query;
+mod reflection;
+pub mod renderer;
 mod resources;
+pub mod settings;
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
 
@@ -165,20 +273,45 @@ pub mod prelude {
     //! `use bevy_tiled_toolkit::prelude::*;` to import commonly used items.
     pub use super::asset_loader::TiledMapAsset;
     pub use super::components::{
-        LayerStorage, TilePos, TileStorage, TileStorageError,
+        CardinalDirection, LayerStorage, ObjectStorage, OrdinalDirection,
+        TileLayerId, TileOffset, TilePos, TileStorage, TileStorageError,
+        TiledLayer, TiledMapStatus, TiledObject, TiledObjectRef,
+        TiledObjectRefs, TiledProperties,
     };
     pub use super::resources::{TiledPoint, TiledPoints};
-    pub use crate::app_extension::TiledComponentReg;
+    pub use crate::app_extension::{TiledComponentReg, TiledLayerRendererReg};
     pub use crate::components::DespawnTiledMap;
+    pub use crate::error::TiledMapError;
+    pub use crate::events::{
+        TiledLayerDespawned, TiledLayerSpawned, TiledMapDespawned, TiledMapSet,
+        TiledMapSpawned, TiledObjectDespawned, TiledObjectSpawned,
+    };
     pub use crate::plugin::TiledMapBundle;
     pub use crate::plugin::TiledToolkitPlugin;
-    pub use crate::TiledComponent;
+    pub use crate::project::{TiledEnumValue, TiledProjectAsset};
+    pub use crate::property_value::TiledPropertyError;
+    pub use crate::query::{TiledMapQuery, TiledTileInfo};
+    pub use crate::settings::{
+        TiledLayerFilter, TiledMapAnchor, TiledMapSpawnSettings,
+        TiledRenderBackend, TiledToolkitSettings,
+    };
+    pub use crate::{TiledComponent, TiledLayerHandler};
+    pub use bevy_tiled_toolkit_macros::{TiledClass, TiledComponent};
+}
+
+pub use bevy_tiled_toolkit_macros::{TiledClass, TiledComponent};
+
+/// Paths used by the code generated with derive macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy;
+    pub use tiled;
 }
 
 /// Spawn your components with specific tiles or objects from Tiled.
 ///
 /// Implement this trait for your component type and add your component's name
-/// to `Class` field in tile's properties in Tiled.
+/// to `Class` field of a tile or an object in Tiled.
 ///
 /// ```
 /// use bevy::prelude::*;
@@ -246,7 +379,107 @@ pub trait TiledComponent {
         values: HashMap<String, tiled::PropertyValue>,
         asset_server: &Res<AssetServer>,
     );
+    /// Same as `insert_self_to_entity`, but the error is sent by the plugin as
+    /// [TiledMapError::ComponentFailed](crate::error::TiledMapError::ComponentFailed).
+    /// `#[derive(TiledComponent)]` implements it, manual implementations
+    /// don't have to.
+    fn try_insert_self_to_entity(
+        &self,
+        commands: &mut EntityCommands,
+        values: HashMap<String, tiled::PropertyValue>,
+        asset_server: &Res<AssetServer>,
+    ) -> Result<(), property_value::TiledPropertyError> {
+        self.insert_self_to_entity(commands, values, asset_server);
+        Ok(())
+    }
     /// This function required for getting name of your type and compare it
     /// with `Class` name from Tiled.
     fn get_class_name(&self) -> &str;
 }
+
+/// Attach behaviour to whole layers with a specific class from Tiled.
+///
+/// Implement this trait and register it with
+/// `app.register_tiled_layer_handler::<T>()`. Then every layer with the same
+/// `Class` as `get_class_name` returns is passed to the handler after spawning.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+/// use bevy_tiled_toolkit::error::TiledErrorContext;
+/// use bevy_tiled_toolkit::renderer::MapSpawnContext;
+/// use bevy_tiled_toolkit::settings::TiledLayerSettings;
+///
+/// #[derive(Component)]
+/// struct Water;
+///
+/// #[derive(Default)]
+/// struct WaterLayer;
+///
+/// impl TiledLayerHandler for WaterLayer {
+///     fn insert_self_to_layer(
+///         &self,
+///         ctx: &mut MapSpawnContext,
+///         layer_entity: Entity,
+///         _layer: &tiled::Layer,
+///         _context: &TiledErrorContext,
+///     ) {
+///         ctx.commands().entity(layer_entity).insert(Water);
+///     }
+///     fn get_class_name(&self) -> &str {
+///         "Water"
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct CollisionLayer;
+///
+/// impl TiledLayerHandler for CollisionLayer {
+///     fn layer_settings(
+///         &self,
+///         _layer: &tiled::Layer,
+///         settings: &mut TiledLayerSettings,
+///     ) {
+///         // Spawn only colliders for this layer.
+///         settings.spawn_sprites = false;
+///         settings.spawn_colliders = true;
+///     }
+///     fn insert_self_to_layer(
+///         &self,
+///         _ctx: &mut MapSpawnContext,
+///         _layer_entity: Entity,
+///         _layer: &tiled::Layer,
+///         _context: &TiledErrorContext,
+///     ) {
+///     }
+///     fn get_class_name(&self) -> &str {
+///         "Collision"
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.register_tiled_layer_handler::<WaterLayer>()
+///     .register_tiled_layer_handler::<CollisionLayer>();
+/// ```
+pub trait TiledLayerHandler {
+    /// This method called before the layer is spawned, alter its resolved
+    /// settings here.
+    fn layer_settings(
+        &self,
+        _layer: &tiled::Layer,
+        _settings: &mut settings::TiledLayerSettings,
+    ) {
+    }
+    /// This method called when the layer with the same `Class` name is
+    /// spawned. Layer properties are available in `layer.properties`.
+    fn insert_self_to_layer(
+        &self,
+        ctx: &mut renderer::MapSpawnContext,
+        layer_entity: Entity,
+        layer: &tiled::Layer,
+        context: &error::TiledErrorContext,
+    );
+    /// This function required for getting name of your type and compare it
+    /// with layer `Class` name from Tiled.
+    fn get_class_name(&self) -> &str;
+}
@@ -0,0 +1,256 @@
+//! This module contains `TiledPickingPlugin`, which sends events about tiles
+//! under the cursor.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+use crate::components::TilePos;
+use crate::events::TiledMapSet;
+use crate::query::TiledMapQuery;
+use crate::settings::TiledToolkitSettings;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Add this plugin with [TiledToolkitPlugin](crate::prelude::TiledToolkitPlugin)
+/// to receive [TileHovered] and [TileClicked] events for the topmost tile
+/// under the cursor. The cursor of the primary window is converted to the
+/// world through the active camera with [TiledPickingCamera], nothing is
+/// picked without it.
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+/// use bevy_tiled_toolkit::picking::{
+///     TileClicked, TiledPickingCamera, TiledPickingPlugin,
+/// };
+///
+/// fn system_select_tile(mut events: EventReader<TileClicked>) {
+///     for event in events.iter() {
+///         info!("Clicked {:?} in layer {}", event.tile_pos, event.layer);
+///     }
+/// }
+///
+/// fn system_spawn_camera(mut commands: Commands) {
+///     commands.spawn((Camera2dBundle::default(), TiledPickingCamera));
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TiledToolkitPlugin::default(),
+///     TiledPickingPlugin::default(),
+/// ))
+/// .add_systems(Startup, system_spawn_camera)
+/// .add_systems(Update, system_select_tile);
+/// ```
+/// Systems run in `TiledToolkitSettings::schedule` after maps are spawned, or
+/// in `Update` without the settings.
+/// Without a window, e.g. in tests, write the world position into
+/// [TiledCursor] yourself, it's not overwritten then.
+pub struct TiledPickingPlugin {
+    /// Mouse button, which sends [TileClicked].
+    pub button: MouseButton,
+}
+
+impl Default for TiledPickingPlugin {
+    fn default() -> Self {
+        TiledPickingPlugin {
+            button: MouseButton::Left,
+        }
+    }
+}
+
+impl Plugin for TiledPickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TileHovered>()
+            .add_event::<TileClicked>()
+            .init_resource::<TiledCursor>()
+            .insert_resource(TiledPickingSettings {
+                button: self.button,
+            });
+    }
+
+    // Settings are read here, so plugins can be added in any order.
+    fn finish(&self, app: &mut App) {
+        let schedule = match app.world.get_resource::<TiledToolkitSettings>() {
+            Some(settings) => settings.schedule.clone(),
+            None => Box::new(Update),
+        };
+        app.add_systems(
+            schedule,
+            (system_update_cursor, system_pick_tiles)
+                .chain()
+                .after(TiledMapSet::Spawn),
+        );
+    }
+}
+
+/// Marks the camera, which converts the cursor into the world. If several
+/// marked cameras are active, the one with the highest `order` is used.
+#[derive(Component, Default, Clone, Copy, Debug)]
+pub struct TiledPickingCamera;
+
+/// Cursor position in the world, `None` if the cursor is outside of the
+/// window.
+#[derive(Resource, Default, Clone, Copy, Debug, PartialEq)]
+pub struct TiledCursor {
+    pub world_position: Option<Vec2>,
+}
+
+/// Sent when the cursor moves onto another tile.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileHovered {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    /// Index of the tile layer.
+    pub layer: usize,
+    pub tile_pos: TilePos,
+    /// Tile entity.
+    pub entity: Entity,
+}
+
+/// Sent when the picking button is pressed over a tile.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TileClicked {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    /// Index of the tile layer.
+    pub layer: usize,
+    pub tile_pos: TilePos,
+    /// Tile entity.
+    pub entity: Entity,
+}
+
+#[derive(Resource)]
+struct TiledPickingSettings {
+    button: MouseButton,
+}
+
+/// Convert the cursor of the primary window into the world.
+fn system_update_cursor(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<TiledPickingCamera>>,
+    mut cursor: ResMut<TiledCursor>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let camera = camera_query
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order);
+    let world_position = camera.and_then(|(camera, transform)| {
+        let position = window.cursor_position()?;
+        camera.viewport_to_world_2d(transform, position)
+    });
+    if cursor.world_position != world_position {
+        cursor.world_position = world_position;
+    }
+}
+
+/// Send events about the topmost tiles under the cursor.
+#[allow(clippy::too_many_arguments)]
+fn system_pick_tiles(
+    cursor: Res<TiledCursor>,
+    buttons: Option<Res<Input<MouseButton>>>,
+    settings: Res<TiledPickingSettings>,
+    map_query: TiledMapQuery,
+    tilemap_query: Query<Entity, With<Handle<TiledMapAsset>>>,
+    mut hovered: Local<Vec<TileHovered>>,
+    mut hovered_events: EventWriter<TileHovered>,
+    mut clicked_events: EventWriter<TileClicked>,
+) {
+    let tiles: Vec<TileHovered> = match cursor.world_position {
+        Some(position) => tilemap_query
+            .iter()
+            .filter_map(|map| map_query.top_tile_at(map, position))
+            .map(|tile| TileHovered {
+                map: tile.map,
+                layer: tile.layer,
+                tile_pos: tile.tile_pos,
+                entity: tile.entity,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    for tile in tiles.iter().filter(|tile| !hovered.contains(tile)) {
+        hovered_events.send(*tile);
+    }
+    let clicked =
+        buttons.map_or(false, |buttons| buttons.just_pressed(settings.button));
+    if clicked {
+        clicked_events.send_batch(tiles.iter().map(|tile| TileClicked {
+            map: tile.map,
+            layer: tile.layer,
+            tile_pos: tile.tile_pos,
+            entity: tile.entity,
+        }));
+    }
+    *hovered = tiles;
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::tests::{initialize_app, spawn_map};
+
+    fn drain<E: Event>(app: &mut App) -> Vec<E> {
+        app.world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    fn set_cursor(app: &mut App, world_position: Option<Vec2>) {
+        app.world.resource_mut::<TiledCursor>().world_position = world_position;
+    }
+
+    #[test]
+    fn test_synthetic_cursor() {
+        let mut app = initialize_app();
+        // Settings are inserted after the plugin, they're read in `finish`.
+        app.add_plugins(TiledPickingPlugin::default())
+            .insert_resource(TiledToolkitSettings::default())
+            .init_resource::<Input<MouseButton>>();
+        app.finish();
+        let map = spawn_map(&mut app, GlobalTransform::IDENTITY, false);
+
+        // Hovering the tile of the top layer
+        set_cursor(&mut app, Some(Vec2::new(24., 40.)));
+        app.update();
+        let hovered = drain::<TileHovered>(&mut app);
+        assert_eq!(hovered.len(), 1);
+        assert_eq!(hovered[0].map, map);
+        assert_eq!(hovered[0].layer, 1);
+        assert_eq!(hovered[0].tile_pos, TilePos::new(1, 2));
+        assert!(drain::<TileClicked>(&mut app).is_empty());
+
+        // Staying on the same tile sends nothing, clicking sends the tile
+        app.world
+            .resource_mut::<Input<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        assert!(drain::<TileHovered>(&mut app).is_empty());
+        let clicked = drain::<TileClicked>(&mut app);
+        assert_eq!(clicked.len(), 1);
+        assert_eq!(clicked[0].entity, hovered[0].entity);
+        app.world.resource_mut::<Input<MouseButton>>().clear();
+
+        // Moving onto the bottom layer tile
+        set_cursor(&mut app, Some(Vec2::new(8., 8.)));
+        app.update();
+        let hovered = drain::<TileHovered>(&mut app);
+        assert_eq!(hovered.len(), 1);
+        assert_eq!(hovered[0].layer, 0);
+        assert_eq!(hovered[0].tile_pos, TilePos::new(0, 0));
+
+        // Leaving the map
+        set_cursor(&mut app, Some(Vec2::new(-8., 8.)));
+        app.update();
+        set_cursor(&mut app, None);
+        app.update();
+        assert!(drain::<TileHovered>(&mut app).is_empty());
+        assert!(drain::<TileClicked>(&mut app).is_empty());
+    }
+}
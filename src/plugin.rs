@@ -15,14 +15,40 @@ use bevy_ecs_tilemap::prelude::*;
 
 use crate::asset_loader::TiledLoader;
 use crate::asset_loader::TiledMapAsset;
+#[cfg(feature = "render")]
 use crate::components::Animation;
+#[cfg(feature = "bevy_ecs_tilemap")]
+use crate::components::CenteredTileGrid;
 use crate::components::LayerStorage;
-use crate::components::NeedToSpawn;
+use crate::components::ObjectStorage;
 use crate::components::TileStorage;
+use crate::components::TiledMapStatus;
+use crate::components::TiledProperties;
+#[cfg(feature = "render")]
 use crate::components::TilesetTexture;
+use crate::components::{
+    TiledLayer, TiledObject, TiledObjectRef, TiledObjectRefs,
+};
+use crate::error::{TiledErrorContext, TiledMapError};
+use crate::events::{
+    TiledLayerDespawned, TiledLayerSpawned, TiledMapDespawned, TiledMapSet,
+    TiledMapSpawned, TiledObjectDespawned, TiledObjectSpawned,
+};
+use crate::factory::TiledComponentContext;
 use crate::prelude::DespawnTiledMap;
 use crate::prelude::TilePos;
-use crate::resources::TiledComponentResource;
+use crate::project::{TiledProjectAsset, TiledProjectLoader};
+use crate::reflection::InsertReflectedComponents;
+use crate::renderer::{
+    MapSpawnContext, PendingObjectRef, SpritesRenderer, TiledLayerRenderer,
+};
+use crate::resources::{
+    TiledComponentResource, TiledLayerHandlerResource,
+    TiledLayerRendererResource, TiledProjectResource,
+};
+use crate::settings::{
+    TiledLayerSettings, TiledMapSpawnSettings, TiledToolkitSettings,
+};
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
 
@@ -35,317 +61,691 @@ pub struct TiledMapBundle {
     pub layers_storage: LayerStorage,
     /// Stores all tiles entities of all layers of the map.
     pub tile_storage: TileStorage,
+    /// Stores all objects entities of the map by id.
+    pub object_storage: ObjectStorage,
+    /// Load lifecycle of this map.
+    pub status: TiledMapStatus,
+    /// Alters spawning of this map instance.
+    pub spawn_settings: TiledMapSpawnSettings,
     pub name: Name,
     pub transform: Transform,
     pub global_transform: GlobalTransform,
+    #[cfg(feature = "render")]
     pub visibility: Visibility,
+    #[cfg(feature = "render")]
     pub computed: ComputedVisibility,
 }
 
-pub struct TiledToolkitPlugin;
+/// Add this plugin to the [App] to load and spawn Tiled maps.
+#[derive(Default)]
+pub struct TiledToolkitPlugin {
+    pub settings: TiledToolkitSettings,
+}
 
 impl Plugin for TiledToolkitPlugin {
-    fn build(&self, mut app: &mut App) {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "bevy_ecs_tilemap")]
         if !app.is_plugin_added::<TilemapPlugin>() {
-            app = app.add_plugins(TilemapPlugin);
+            app.add_plugins(TilemapPlugin);
         }
+        let schedule = self.settings.schedule.clone();
         app
             // Custom asset loaders
             .add_asset_loader(TiledLoader)
+            .add_asset_loader(TiledProjectLoader)
             // Assets
             .add_asset::<TiledMapAsset>()
-            // States
-            .add_state::<TiledMapLoadState>()
+            .add_asset::<TiledProjectAsset>()
+            // Types
+            .register_type::<TiledMapStatus>()
+            // Events
+            .add_event::<TiledMapError>()
+            .add_event::<TiledMapSpawned>()
+            .add_event::<TiledLayerSpawned>()
+            .add_event::<TiledObjectSpawned>()
+            .add_event::<TiledMapDespawned>()
+            .add_event::<TiledLayerDespawned>()
+            .add_event::<TiledObjectDespawned>()
             // Resources
             .init_resource::<TiledComponentResource>()
+            .init_resource::<TiledLayerHandlerResource>()
+            .init_resource::<TiledLayerRendererResource>()
+            .insert_resource(self.settings.clone())
             // Systems
-            .add_systems(
-                Update,
+            .add_systems(Startup, system_load_project)
+            .configure_sets(
+                schedule.clone(),
                 (
-                    system_despawn_maps
-                        .run_if(in_state(TiledMapLoadState::Idle)),
-                    system_check_asset_state
-                        .run_if(in_state(TiledMapLoadState::Idle)),
-                    system_setup_atlases
-                        .run_if(in_state(TiledMapLoadState::SetupAtlases)),
-                    system_process_loaded_maps
-                        .run_if(in_state(TiledMapLoadState::Idle)),
-                    system_animate_entities,
+                    TiledMapSet::Despawn,
+                    TiledMapSet::Load,
+                    TiledMapSet::Spawn,
+                    TiledMapSet::Animate,
                 )
                     .chain(),
+            )
+            .add_systems(
+                schedule.clone(),
+                (
+                    (
+                        system_despawn_maps,
+                        system_check_asset_state,
+                        system_forget_despawned_objects,
+                    )
+                        .chain()
+                        .in_set(TiledMapSet::Despawn),
+                    (system_check_load_state, system_setup_atlases)
+                        .chain()
+                        .in_set(TiledMapSet::Load),
+                    (system_process_loaded_maps, apply_deferred)
+                        .chain()
+                        .in_set(TiledMapSet::Spawn),
+                ),
             );
+        #[cfg(feature = "render")]
+        app.add_systems(
+            schedule,
+            system_animate_entities.in_set(TiledMapSet::Animate),
+        );
     }
 }
 
-#[derive(States, Default, Debug, Hash, PartialEq, Eq, Clone)]
-enum TiledMapLoadState {
-    #[default]
-    Idle,
-    SetupAtlases,
+/// Start loading the project from settings.
+fn system_load_project(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<TiledToolkitSettings>,
+) {
+    if let Some(ref path) = settings.project {
+        commands.insert_resource(TiledProjectResource {
+            handle: asset_server.load(path.as_str()),
+        });
+    }
 }
 
+/// Tear down maps, which asset was reloaded, and start loading them again.
 fn system_check_asset_state(
     mut commands: Commands,
-    mut tilemap_query: Query<
-        (&Handle<TiledMapAsset>, &mut TileStorage, &mut LayerStorage),
-        Without<NeedToSpawn>,
-    >,
-    mut tilemaps: ResMut<Assets<TiledMapAsset>>,
-    mut next_state: ResMut<NextState<TiledMapLoadState>>,
+    mut tilemap_query: Query<(
+        Entity,
+        &Handle<TiledMapAsset>,
+        &mut TileStorage,
+        &mut ObjectStorage,
+        &mut LayerStorage,
+        &mut TiledMapStatus,
+    )>,
+    tilemaps: Res<Assets<TiledMapAsset>>,
     maps_events: EventReader<AssetEvent<TiledMapAsset>>,
+    mut despawned_events: EventWriter<TiledMapDespawned>,
+    mut layer_events: EventWriter<TiledLayerDespawned>,
+    mut object_events: EventWriter<TiledObjectDespawned>,
 ) {
     let changed_maps = events_to_vectors(maps_events);
     let changed_existing = tilemap_query
         .iter_mut()
-        .filter(|(handle, _, _)| changed_maps.contains(handle));
-
-    for (tilemap_handle, mut tile_storage, mut layer_storage) in
-        changed_existing
+        .filter(|(_, handle, _, _, _, status)| {
+            **status != TiledMapStatus::Loading && changed_maps.contains(handle)
+        })
+        // A freshly loaded asset always has `atlases_loaded == false`, it
+        // becomes `true` only when we write atlases into it ourselves, and
+        // that write is not a reason to respawn the map.
+        .filter(|(_, handle, _, _, _, _)| {
+            !tilemaps.get(handle).map_or(false, |map| map.atlases_loaded)
+        });
+
+    for (
+        entity,
+        handle,
+        mut tile_storage,
+        mut object_storage,
+        mut layer_storage,
+        mut status,
+    ) in changed_existing
     {
-        // for ecs_storage in tile_storage.bevy_ecs_tilemap_tile_storages.values()
-        // {
-        //     for tile in ecs_storage.iter().flatten() {
-        //         // In `bevy_ecs_tilamap` there is no point to add childrens to
-        //         // it, they don't have `transform` component. That's why we
-        //         // call `despawn()` instead of `despawn_recursive()`.
-        //         commands.entity(*tile).despawn();
-        //     }
-        // }
-        // for tile in tile_storage.iter_all().flatten() {
-        //     commands.entity(*tile).despawn();
-        // }
-        // Clear storages
-
-        for layer in layer_storage.layers.values() {
+        for (_, layer) in layer_storage.iter() {
             // Layer has objects as children, despawn them too.
-            commands.entity(*layer).despawn_recursive();
+            commands.entity(layer).despawn_recursive();
+        }
+
+        if *status == TiledMapStatus::Ready {
+            send_despawn_events(
+                entity,
+                handle,
+                &layer_storage,
+                &object_storage,
+                &mut layer_events,
+                &mut object_events,
+            );
+            despawned_events.send(TiledMapDespawned {
+                map: entity,
+                handle: handle.clone_weak(),
+            });
         }
 
+        // Clear storages
         tile_storage.clear();
-        tile_storage.bevy_ecs_tilemap_tile_storages.clear();
-        layer_storage.layers.clear();
+        object_storage.clear();
+        layer_storage.clear();
+        *status = TiledMapStatus::Loading;
+    }
+}
 
-        if let Some(tilemap_asset) = tilemaps.get_mut(tilemap_handle) {
-            tilemap_asset.atlases_loaded = false;
+/// Move maps, which asset is loaded, to the `BuildingAtlases` status.
+fn system_check_load_state(
+    mut tilemap_query: Query<(&Handle<TiledMapAsset>, &mut TiledMapStatus)>,
+    tilemaps: Res<Assets<TiledMapAsset>>,
+    asset_server: Res<AssetServer>,
+    mut errors: EventWriter<TiledMapError>,
+) {
+    for (tilemap_handle, mut status) in tilemap_query.iter_mut() {
+        if *status != TiledMapStatus::Loading {
+            continue;
+        }
+        match asset_server.get_load_state(tilemap_handle) {
+            LoadState::Failed => {
+                report_error(
+                    &mut errors,
+                    TiledMapError::LoadFailed {
+                        context: TiledErrorContext::new(tilemap_handle),
+                    },
+                );
+                *status = TiledMapStatus::Failed;
+            }
+            // Handles created with `Assets::add` are always `NotLoaded`.
+            _ if tilemaps.contains(tilemap_handle) => {
+                *status = TiledMapStatus::BuildingAtlases;
+            }
+            _ => {}
         }
-        println!("Next state stupatlases");
-        next_state.set(TiledMapLoadState::SetupAtlases);
     }
 }
 
-/// Slice all textures into atlases
+/// Slice all textures into atlases, when tileset images are loaded.
+#[cfg(feature = "render")]
 fn system_setup_atlases(
-    mut commands: Commands,
-    tilemap_query: Query<(Entity, &Handle<TiledMapAsset>)>,
+    mut tilemap_query: Query<(&Handle<TiledMapAsset>, &mut TiledMapStatus)>,
     mut tilemaps: ResMut<Assets<TiledMapAsset>>,
     mut textures: ResMut<Assets<Image>>,
     asset_server: Res<AssetServer>,
     mut texture_atlas_assets: ResMut<Assets<TextureAtlas>>,
-    mut next_state: ResMut<NextState<TiledMapLoadState>>,
+    mut errors: EventWriter<TiledMapError>,
 ) {
-    println!("setuping atlases");
-    if tilemap_query.iter().all(|(_, tilemap_handle)| {
-        if let Some(tilemap_asset) = tilemaps.get(tilemap_handle) {
-            tilemap_asset.atlases_loaded
-        } else {
-            false
-        }
-    }) {
-        next_state.set(TiledMapLoadState::Idle);
-    }
-
-    for (entity, tilemap_handle) in tilemap_query.iter() {
-        if !(LoadState::Loaded == asset_server.get_load_state(tilemap_handle)) {
-            warn!("TiledMapAsset were not loaded for now");
+    for (tilemap_handle, mut status) in tilemap_query.iter_mut() {
+        if *status != TiledMapStatus::BuildingAtlases {
             continue;
         }
-        let Some(tilemap_asset) = tilemaps.get_mut(tilemap_handle) else {
-            warn!("No TiledMapAsset existing with that handle!");
+        let Some(tilemap_asset) = tilemaps.get(tilemap_handle) else {
             continue;
         };
+        // Another map entity with the same asset already built atlases.
         if tilemap_asset.atlases_loaded {
+            *status = TiledMapStatus::Spawning;
             continue;
         }
 
-        // Clear old values after changing
-        tilemap_asset.atlases.clear();
-        tilemap_asset.atlases_offsets.clear();
+        let map_context = TiledErrorContext::new(tilemap_handle);
+        let images = tilemap_asset
+            .tilemap_textures
+            .values()
+            .flat_map(|texture| match texture {
+                TilesetTexture::Single(handle) => vec![handle.id()],
+                TilesetTexture::Vector(handles) => {
+                    handles.iter().map(|handle| handle.id()).collect()
+                }
+            })
+            .collect::<Vec<_>>();
+        match asset_server.get_group_load_state(images) {
+            LoadState::Loaded => {}
+            LoadState::Failed => {
+                report_error(
+                    &mut errors,
+                    TiledMapError::LoadFailed {
+                        context: map_context,
+                    },
+                );
+                *status = TiledMapStatus::Failed;
+                continue;
+            }
+            // Wait for images without blocking other maps.
+            _ => continue,
+        }
 
-        for (tls_idx, tls) in tilemap_asset.map.tilesets().iter().enumerate() {
-            // In this case there is expected single spritesheet image
-            if let Some(ref tls_image) = tls.image {
-                let handle = match tilemap_asset.tilemap_textures.get(&tls_idx)
-                {
-                    Some(TilesetTexture::Single(handle)) => handle,
-                    _ => panic!("Error: tilemap spritesheet was not loaded!"),
-                };
+        let Some(tilemap_asset) = tilemaps.get_mut(tilemap_handle) else {
+            continue;
+        };
+        build_atlases(
+            tilemap_asset,
+            &mut textures,
+            &mut texture_atlas_assets,
+            &map_context,
+            &mut errors,
+        );
+        *status = TiledMapStatus::Spawning;
+    }
+}
 
-                let tile_size =
-                    Vec2::new(tls.tile_width as f32, tls.tile_height as f32);
-                let columns = tls.columns as usize;
-                let padding = Vec2::splat(tls.spacing as f32);
-                let rows = ((tls_image.height - tls.margin as i32 * 2)
-                    / (tls.tile_height + tls.spacing) as i32)
-                    as usize;
-                let offset =
-                    Vec2::new(tls.offset_x as f32, tls.offset_y as f32);
-                let atlas = TextureAtlas::from_grid(
-                    handle.clone(),
-                    tile_size,
-                    columns,
-                    rows,
-                    Some(padding),
-                    Some(offset),
-                );
-                let handle = texture_atlas_assets.add(atlas);
-                tilemap_asset.atlases.insert(tls_idx, handle);
-            } else {
-                // In this case there is expected vec with individual images
-                let handles = match tilemap_asset.tilemap_textures.get(&tls_idx)
-                {
-                    Some(TilesetTexture::Vector(handles)) => handles,
-                    _ => panic!("Error: individual images were not loaded!"),
-                };
-                // FIXME: detect required size of atlasbuilder
-                let mut atlas_builder = TextureAtlasBuilder::default()
-                    .max_size(Vec2::new(512. * 20., 512.));
-                // Individual image to tile-id offset container
-                let offsets = &tilemap_asset.tile_image_offsets;
-
-                // Because of `TextureAtlasBuilder` saves all images in random
-                // order, we need to check and save all image offsets in atlas.
-                let mut atlas_offsets = Vec::new();
-                // Pack images to atlas
-                for (tile_id, _) in tls.tiles() {
-                    let offset = offsets.get(&(tls_idx, tile_id)).unwrap();
-                    let handle = handles.get(*offset as usize).unwrap();
-                    let Some(texture) = textures.get(handle) else {
-                    warn!("TextureAtlasBuilder: missing image: {:?}.",
-                        asset_server.get_handle_path(handle));
+/// Without rendering there are no images to wait for and no atlases to build.
+#[cfg(not(feature = "render"))]
+fn system_setup_atlases(mut tilemap_query: Query<&mut TiledMapStatus>) {
+    for mut status in tilemap_query.iter_mut() {
+        if *status == TiledMapStatus::BuildingAtlases {
+            *status = TiledMapStatus::Spawning;
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+fn build_atlases(
+    tilemap_asset: &mut TiledMapAsset,
+    textures: &mut Assets<Image>,
+    texture_atlas_assets: &mut Assets<TextureAtlas>,
+    map_context: &TiledErrorContext,
+    errors: &mut EventWriter<TiledMapError>,
+) {
+    // Clear old values after changing
+    tilemap_asset.atlases.clear();
+    tilemap_asset.atlases_offsets.clear();
+
+    for (tls_idx, tls) in tilemap_asset.map.tilesets().iter().enumerate() {
+        // In this case there is expected single spritesheet image
+        if let Some(ref tls_image) = tls.image {
+            let handle = match tilemap_asset.tilemap_textures.get(&tls_idx) {
+                Some(TilesetTexture::Single(handle)) => handle,
+                _ => {
+                    report_error(
+                        errors,
+                        TiledMapError::MissingTilesetTexture {
+                            context: map_context.clone(),
+                            tileset_idx: tls_idx,
+                        },
+                    );
                     continue;
-                };
-                    info!(
-                        "Adding texture with offset {}, and id {} to atlas.",
-                        offset, tile_id
+                }
+            };
+
+            let tile_size =
+                Vec2::new(tls.tile_width as f32, tls.tile_height as f32);
+            let columns = tls.columns as usize;
+            let padding = Vec2::splat(tls.spacing as f32);
+            let rows = ((tls_image.height - tls.margin as i32 * 2)
+                / (tls.tile_height + tls.spacing) as i32)
+                as usize;
+            let offset = Vec2::new(tls.offset_x as f32, tls.offset_y as f32);
+            let atlas = TextureAtlas::from_grid(
+                handle.clone(),
+                tile_size,
+                columns,
+                rows,
+                Some(padding),
+                Some(offset),
+            );
+            let handle = texture_atlas_assets.add(atlas);
+            tilemap_asset.atlases.insert(tls_idx, handle);
+        } else {
+            // In this case there is expected vec with individual images
+            let handles = match tilemap_asset.tilemap_textures.get(&tls_idx) {
+                Some(TilesetTexture::Vector(handles)) => handles,
+                _ => {
+                    report_error(
+                        errors,
+                        TiledMapError::MissingTilesetTexture {
+                            context: map_context.clone(),
+                            tileset_idx: tls_idx,
+                        },
                     );
-                    atlas_builder.add_texture(handle.clone(), texture);
-                    atlas_offsets.push((tile_id, handle.clone()));
+                    continue;
                 }
-                let atlas = atlas_builder
-                    .finish(&mut textures)
-                    .expect("Error: can't build atlas.");
-
-                // Write all atlas offsets to hashmap.
-                let mut offsets = HashMap::new();
-                for (tile_id, handle) in atlas_offsets {
-                    offsets.insert(
-                        tile_id,
-                        atlas.get_texture_index(&handle).unwrap(),
+            };
+            // FIXME: detect required size of atlasbuilder
+            let mut atlas_builder = TextureAtlasBuilder::default()
+                .max_size(Vec2::new(512. * 20., 512.));
+            // Individual image to tile-id offset container
+            let offsets = &tilemap_asset.tile_image_offsets;
+
+            // Because of `TextureAtlasBuilder` saves all images in random
+            // order, we need to check and save all image offsets in atlas.
+            let mut atlas_offsets = Vec::new();
+            // Pack images to atlas
+            for (tile_id, _) in tls.tiles() {
+                let Some(handle) = offsets
+                    .get(&(tls_idx, tile_id))
+                    .and_then(|offset| handles.get(*offset as usize))
+                else {
+                    // Tile without image, nothing to pack.
+                    continue;
+                };
+                let Some(texture) = textures.get(handle) else {
+                    report_error(
+                        errors,
+                        TiledMapError::MissingTilesetTexture {
+                            context: map_context.with_tile(tls_idx, tile_id),
+                            tileset_idx: tls_idx,
+                        },
+                    );
+                    continue;
+                };
+                atlas_builder.add_texture(handle.clone(), texture);
+                atlas_offsets.push((tile_id, handle.clone()));
+            }
+            let atlas = match atlas_builder.finish(textures) {
+                Ok(atlas) => atlas,
+                Err(e) => {
+                    report_error(
+                        errors,
+                        TiledMapError::AtlasBuildFailed {
+                            context: map_context.clone(),
+                            tileset_idx: tls_idx,
+                            reason: format!("{:?}", e),
+                        },
                     );
+                    continue;
                 }
-                // We can have many individual-image based tilesets.
-                tilemap_asset.atlases_offsets.insert(tls_idx, offsets);
+            };
 
-                // Store atlas handle with it's tileset index into `tilemap_asset`.
-                let handle = texture_atlas_assets.add(atlas);
-                tilemap_asset.atlases.insert(tls_idx, handle);
-            }
+            // Write all atlas offsets to hashmap.
+            let offsets = atlas_offsets
+                .into_iter()
+                .filter_map(|(tile_id, handle)| {
+                    atlas.get_texture_index(&handle).map(|idx| (tile_id, idx))
+                })
+                .collect::<HashMap<_, _>>();
+            // We can have many individual-image based tilesets.
+            tilemap_asset.atlases_offsets.insert(tls_idx, offsets);
+
+            // Store atlas handle with it's tileset index into `tilemap_asset`.
+            let handle = texture_atlas_assets.add(atlas);
+            tilemap_asset.atlases.insert(tls_idx, handle);
         }
-        tilemap_asset.atlases_loaded = true;
-        commands.entity(entity).insert(NeedToSpawn);
     }
+    tilemap_asset.atlases_loaded = true;
 }
 
 fn system_despawn_maps(
     mut commands: Commands,
-    despawned_tilemaps: Query<(Entity, &LayerStorage), With<DespawnTiledMap>>,
+    despawned_tilemaps: Query<
+        (
+            Entity,
+            &Handle<TiledMapAsset>,
+            &LayerStorage,
+            &ObjectStorage,
+        ),
+        With<DespawnTiledMap>,
+    >,
+    mut despawned_events: EventWriter<TiledMapDespawned>,
+    mut layer_events: EventWriter<TiledLayerDespawned>,
+    mut object_events: EventWriter<TiledObjectDespawned>,
 ) {
     // Despawn tilemaps
-    for (entity, layer_storage) in despawned_tilemaps.iter() {
-        for layer in layer_storage.layers.values() {
-            commands.entity(*layer).despawn_recursive();
+    for (entity, handle, layer_storage, object_storage) in
+        despawned_tilemaps.iter()
+    {
+        for (_, layer) in layer_storage.iter() {
+            commands.entity(layer).despawn_recursive();
         }
         commands.entity(entity).despawn();
+        send_despawn_events(
+            entity,
+            handle,
+            layer_storage,
+            object_storage,
+            &mut layer_events,
+            &mut object_events,
+        );
+        despawned_events.send(TiledMapDespawned {
+            map: entity,
+            handle: handle.clone_weak(),
+        });
+    }
+}
+
+/// Sends despawn events for all layers and objects of the map.
+fn send_despawn_events(
+    map: Entity,
+    handle: &Handle<TiledMapAsset>,
+    layer_storage: &LayerStorage,
+    object_storage: &ObjectStorage,
+    layer_events: &mut EventWriter<TiledLayerDespawned>,
+    object_events: &mut EventWriter<TiledObjectDespawned>,
+) {
+    layer_events.send_batch(layer_storage.iter().map(|(layer_idx, layer)| {
+        TiledLayerDespawned {
+            map,
+            handle: handle.clone_weak(),
+            layer,
+            id: layer_storage.id_of(layer_idx).unwrap_or_default(),
+            name: layer_storage.name_of(layer_idx).unwrap_or_default().into(),
+            class: layer_storage.class_of(layer_idx).map(str::to_string),
+        }
+    }));
+    object_events.send_batch(object_storage.iter().map(|(id, object)| {
+        TiledObjectDespawned {
+            map,
+            handle: handle.clone_weak(),
+            object,
+            id,
+            name: object_storage.name_of(id).unwrap_or_default().into(),
+            class: object_storage.class_of(id).unwrap_or_default().into(),
+        }
+    }));
+}
+
+/// Remove objects, despawned by user, from object storages.
+fn system_forget_despawned_objects(
+    mut removed_objects: RemovedComponents<TiledObject>,
+    mut tilemap_query: Query<(
+        Entity,
+        &Handle<TiledMapAsset>,
+        &mut ObjectStorage,
+    )>,
+    mut object_events: EventWriter<TiledObjectDespawned>,
+) {
+    for entity in removed_objects.iter() {
+        for (map, handle, mut object_storage) in tilemap_query.iter_mut() {
+            // Check first, to not trigger change detection of other maps.
+            if let Some(id) = object_storage.get_id(entity) {
+                let event = TiledObjectDespawned {
+                    map,
+                    handle: handle.clone_weak(),
+                    object: entity,
+                    id,
+                    name: object_storage.name_of(id).unwrap_or_default().into(),
+                    class: object_storage
+                        .class_of(id)
+                        .unwrap_or_default()
+                        .into(),
+                };
+                object_storage.remove_entity(entity);
+                object_events.send(event);
+                break;
+            }
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn system_process_loaded_maps(
     mut commands: Commands,
-    maps: ResMut<Assets<TiledMapAsset>>,
-    mut tile_map_query: Query<
-        (
-            Entity,
-            &Handle<TiledMapAsset>,
-            &mut TileStorage,
-            &mut LayerStorage,
-        ),
-        With<NeedToSpawn>,
-    >,
+    maps: Res<Assets<TiledMapAsset>>,
+    mut tile_map_query: Query<(
+        Entity,
+        &Handle<TiledMapAsset>,
+        &mut TileStorage,
+        &mut ObjectStorage,
+        &mut LayerStorage,
+        &mut TiledMapStatus,
+        Option<&TiledMapSpawnSettings>,
+    )>,
     asset_server: Res<AssetServer>,
-    mut tiled_components: Res<TiledComponentResource>,
+    tiled_components: Res<TiledComponentResource>,
+    layer_handlers: Res<TiledLayerHandlerResource>,
+    renderers: Res<TiledLayerRendererResource>,
+    settings: Res<TiledToolkitSettings>,
+    project: Option<Res<TiledProjectResource>>,
+    projects: Res<Assets<TiledProjectAsset>>,
+    mut errors: EventWriter<TiledMapError>,
+    mut map_events: EventWriter<TiledMapSpawned>,
+    mut layer_events: EventWriter<TiledLayerSpawned>,
+    mut object_events: EventWriter<TiledObjectSpawned>,
 ) {
-    for (map_entity, map_handle, mut tile_storage, mut layer_storage) in
-        tile_map_query.iter_mut()
+    let project = match project {
+        Some(project) => match projects.get(&project.handle) {
+            Some(project) => Some(project),
+            None if asset_server.get_load_state(&project.handle)
+                == LoadState::Failed =>
+            {
+                None
+            }
+            // Wait for the project to merge its defaults into properties
+            None => return,
+        },
+        None => None,
+    };
+    let default_spawn_settings = TiledMapSpawnSettings::default();
+    for (
+        map_entity,
+        map_handle,
+        mut tile_storage,
+        mut object_storage,
+        mut layer_storage,
+        mut status,
+        spawn_settings,
+    ) in tile_map_query.iter_mut()
     {
+        if *status != TiledMapStatus::Spawning {
+            continue;
+        }
         // If handle is existing, get actual `TiledMap`
         let Some(tilemap_asset) = maps.get(map_handle) else {
             log::warn!("Cant get tiled_map from Assets<TiledMap>!");
+            *status = TiledMapStatus::Loading;
             continue;
         };
 
+        let map_context = TiledErrorContext::new(map_handle);
+        if tilemap_asset.map.orientation != tiled::Orientation::Orthogonal {
+            report_error(
+                &mut errors,
+                TiledMapError::UnsupportedOrientation {
+                    context: map_context,
+                    orientation: tilemap_asset.map.orientation,
+                },
+            );
+            *status = TiledMapStatus::Failed;
+            continue;
+        }
+
+        let mut ctx = MapSpawnContext {
+            commands: &mut commands,
+            asset_server: &asset_server,
+            tiled_components: &tiled_components,
+            layer_handlers: &layer_handlers,
+            renderers: &renderers,
+            settings: &settings,
+            spawn_settings: spawn_settings.unwrap_or(&default_spawn_settings),
+            tilemap_asset,
+            project,
+            map_entity,
+            map_handle,
+            tile_storage: &mut tile_storage,
+            object_storage: &mut object_storage,
+            errors: Vec::new(),
+            spawned_objects: Vec::new(),
+            object_refs: Vec::new(),
+        };
+
+        // Map class and properties
+        let map = &tilemap_asset.map;
+        let map_properties =
+            TiledProperties::new(map.user_type.as_deref(), &map.properties)
+                .with_defaults(project)
+                .with_files(&tilemap_asset.files);
+        spawn_tiled_components(
+            &mut ctx,
+            map_entity,
+            &[map_properties.class.as_deref().unwrap_or_default()],
+            map_properties.clone(),
+            ComponentSource::default(),
+            &map_context,
+        );
+        ctx.commands.entity(map_entity).insert(map_properties);
+
         // Iterate over layers
         for (layer_idx, layer) in tilemap_asset.map.layers().enumerate() {
+            if !ctx.spawn_settings.spawns_layer(&layer) {
+                continue;
+            }
             let layer_entity = spawn_layer(
+                &mut ctx,
                 layer,
                 layer_idx,
-                &mut commands,
-                &asset_server,
-                tilemap_asset,
-                &mut tiled_components,
-                &mut tile_storage,
+                &map_context.with_layer(layer_idx, &layer.name),
             );
-            let layer_name = Name::new(layer.name.clone());
-
-            layer_storage
-                .layers
-                .insert(layer_name.clone(), layer_entity);
-            commands.entity(layer_entity).insert(layer_name);
-            commands
+            layer_storage.set(
+                layer_idx,
+                layer_entity,
+                layer.id,
+                &layer.name,
+                layer.user_type.as_deref(),
+            );
+            ctx.commands
+                .entity(layer_entity)
+                .insert(Name::new(layer.name.clone()));
+            ctx.commands
                 .entity(map_entity)
-                .push_children(&[layer_entity])
-                .remove::<NeedToSpawn>();
+                .push_children(&[layer_entity]);
+
+            layer_events.send(TiledLayerSpawned {
+                map: map_entity,
+                handle: map_handle.clone_weak(),
+                layer: layer_entity,
+                id: layer.id,
+                name: layer.name.clone(),
+                class: layer.user_type.clone(),
+            });
         }
+
+        resolve_object_refs(&mut ctx);
+
+        errors.send_batch(ctx.errors);
+        object_events.send_batch(ctx.spawned_objects);
+        map_events.send(TiledMapSpawned {
+            map: map_entity,
+            handle: map_handle.clone_weak(),
+        });
+        *status = TiledMapStatus::Ready;
     }
 }
 
-fn spawn_with_bevy_ecs_tilemap(
-    commands: &mut Commands,
+#[cfg(feature = "bevy_ecs_tilemap")]
+pub(crate) fn spawn_with_bevy_ecs_tilemap(
+    ctx: &mut MapSpawnContext,
     layer: &tiled::Layer,
     layer_idx: usize,
-    tilemap_asset: &TiledMapAsset,
-    tile_storage: &mut TileStorage,
+    layer_settings: &TiledLayerSettings,
+    context: &TiledErrorContext,
 ) -> Entity {
-    let layer_entity = commands.spawn_empty().id();
+    let tilemap_asset = ctx.tilemap_asset;
+    let layer_entity = ctx.commands.spawn_empty().id();
     let layer_opacity = layer.opacity;
     let offset_x = layer.offset_x;
     let offset_y = layer.offset_y;
     match layer.layer_type() {
         tiled::LayerType::Tiles(layer) => match layer {
             tiled::TileLayer::Infinite(_) => {
-                panic!("Infinite layers unsupported!")
+                ctx.report(TiledMapError::InfiniteLayer {
+                    context: context.clone(),
+                })
             }
             tiled::TileLayer::Finite(layer_data) => {
                 let tile_width = tilemap_asset.map.tile_width as i32;
                 let tile_height = tilemap_asset.map.tile_height as i32;
                 let layer_tile = match get_first_tile(
-                    layer.width().unwrap() as i32,
-                    layer.height().unwrap() as i32,
+                    layer_data.width() as i32,
+                    layer_data.height() as i32,
                     layer_data,
                 ) {
                     Some(t) => t,
                     None => {
-                        // Skip empty tile
-                        println!("Skipping empty tile");
+                        // Skip empty layer
                         return layer_entity;
                     }
                 };
@@ -355,10 +755,10 @@ fn spawn_with_bevy_ecs_tilemap(
                     match tilemap_asset.tilemap_textures.get(&tls_idx) {
                         Some(t) => t.clone(),
                         None => {
-                            error!(
-                                "There are no atlas for tilemap with index {}",
-                                tls_idx
-                            );
+                            ctx.report(TiledMapError::MissingTilesetTexture {
+                                context: context.clone(),
+                                tileset_idx: tls_idx,
+                            });
                             return layer_entity;
                         }
                     };
@@ -368,43 +768,85 @@ fn spawn_with_bevy_ecs_tilemap(
                 };
                 let mut ecs_tile_storage =
                     bevy_ecs_tilemap::prelude::TileStorage::empty(map_size);
+                let layer_size = UVec2::new(map_size.x, map_size.y);
+                if let Err(e) =
+                    ctx.tile_storage.init_place(layer_idx, layer_size)
+                {
+                    ctx.report(TiledMapError::TileStorage {
+                        context: context.clone(),
+                        error: e,
+                    });
+                }
 
                 for x in 0..map_size.x {
                     for y in 0..map_size.y {
-                        // Transform TMX coords into bevy coords.
-                        let mapped_y = tilemap_asset.map.height - 1 - y;
-
-                        let mapped_x = x as i32;
-                        let mapped_y = mapped_y as i32;
-
-                        let layer_tile =
-                            match layer_data.get_tile(mapped_x, mapped_y) {
-                                Some(t) => t,
-                                None => {
-                                    // Skip empty tile
-                                    continue;
-                                }
-                            };
+                        let tile_pos = TilePos::new(x, y);
+                        let tiled_pos = tile_pos.to_tiled(layer_size);
+
+                        let layer_tile = match layer_data
+                            .get_tile(tiled_pos.x as i32, tiled_pos.y as i32)
+                        {
+                            Some(t) => t,
+                            None => {
+                                // Skip empty tile
+                                continue;
+                            }
+                        };
                         let texture_index = match tileset_texture {
                             TilesetTexture::Single(_) => layer_tile.id(),
-                            TilesetTexture::Vector(_) =>
-                            *tilemap_asset.tile_image_offsets.get(&(tls_idx, layer_tile.id()))
-                            .expect("The offset into to image vector should have been saved during the initial load."),
+                            TilesetTexture::Vector(_) => {
+                                match tilemap_asset
+                                    .tile_image_offsets
+                                    .get(&(tls_idx, layer_tile.id()))
+                                {
+                                    Some(offset) => *offset,
+                                    None => {
+                                        ctx.report(
+                                            TiledMapError::MissingTilesetTexture {
+                                                context: context.with_tile(
+                                                    tls_idx,
+                                                    layer_tile.id(),
+                                                ),
+                                                tileset_idx: tls_idx,
+                                            },
+                                        );
+                                        continue;
+                                    }
+                                }
+                            }
                         };
-                        let tile_pos =
+                        // Both crates use the bottom-left origin.
+                        let ecs_tile_pos =
                             bevy_ecs_tilemap::prelude::TilePos { x, y };
-                        let tile_entity = commands
-                            .spawn(TileBundle {
-                                position: tile_pos,
-                                tilemap_id: TilemapId(layer_entity),
-                                texture_index: TileTextureIndex(texture_index),
-                                color: TileColor(
-                                    Color::WHITE.with_a(layer_opacity),
-                                ),
-                                ..default()
-                            })
+                        let tile_entity = ctx
+                            .commands
+                            .spawn((
+                                TileBundle {
+                                    position: ecs_tile_pos,
+                                    tilemap_id: TilemapId(layer_entity),
+                                    texture_index: TileTextureIndex(
+                                        texture_index,
+                                    ),
+                                    color: TileColor(
+                                        Color::WHITE.with_a(layer_opacity),
+                                    ),
+                                    ..default()
+                                },
+                                tile_pos,
+                            ))
                             .id();
-                        ecs_tile_storage.set(&tile_pos, tile_entity);
+                        ecs_tile_storage.set(&ecs_tile_pos, tile_entity);
+                        if let Err(e) = ctx.tile_storage.set(
+                            layer_idx,
+                            &tile_pos,
+                            tile_entity,
+                        ) {
+                            ctx.report(TiledMapError::TileStorage {
+                                context: context
+                                    .with_tile(tls_idx, layer_tile.id()),
+                                error: e,
+                            });
+                        }
                     }
                 }
 
@@ -420,205 +862,296 @@ fn spawn_with_bevy_ecs_tilemap(
                 let grid_size = tile_size.into();
                 let map_type = TilemapType::default();
 
-                commands
+                ctx.commands
                     .entity(layer_entity)
-                    .insert(TilemapBundle {
-                        grid_size,
-                        map_type,
-                        size: map_size,
-                        storage: ecs_tile_storage.clone(),
-                        texture,
-                        tile_size,
-                        transform: Transform::from_xyz(
-                            tile_width as f32 * 0.5,
-                            tile_height as f32 * 0.5,
-                            layer_idx as f32,
-                        ) * Transform::from_xyz(
-                            offset_x, -offset_y, 0.0,
-                        ),
-                        ..default()
-                    })
+                    .insert((
+                        CenteredTileGrid,
+                        TilemapBundle {
+                            grid_size,
+                            map_type,
+                            size: map_size,
+                            storage: ecs_tile_storage.clone(),
+                            texture,
+                            tile_size,
+                            // `bevy_ecs_tilemap` places tile centers at grid
+                            // points, shift them to match sprite layers.
+                            transform: layer_settings.layer_transform(
+                                Vec2::new(
+                                    offset_x + tile_width as f32 * 0.5,
+                                    -offset_y + tile_height as f32 * 0.5,
+                                ),
+                            ),
+                            ..default()
+                        },
+                    ))
                     .push_children(
                         &ecs_tile_storage
                             .iter()
                             .flatten()
-                            .map(|&e| e)
+                            .copied()
                             .collect::<Vec<_>>()[..],
                     );
-                tile_storage
-                    .bevy_ecs_tilemap_tile_storages
-                    .insert(layer_idx, ecs_tile_storage);
             }
         },
-        _ => error!("bevy_ecs_tilemap supports only LayerType::Tiles layers!"),
+        // bevy_ecs_tilemap renders only tile layers
+        tiled::LayerType::Objects(_) => {
+            ctx.report(TiledMapError::UnsupportedLayerType {
+                context: context.clone(),
+                layer_type: "Object",
+            })
+        }
+        tiled::LayerType::Image(_) => {
+            ctx.report(TiledMapError::UnsupportedLayerType {
+                context: context.clone(),
+                layer_type: "Image",
+            })
+        }
+        tiled::LayerType::Group(_) => {
+            ctx.report(TiledMapError::UnsupportedLayerType {
+                context: context.clone(),
+                layer_type: "Group",
+            })
+        }
     }
     layer_entity
 }
 
 fn spawn_layer(
+    ctx: &mut MapSpawnContext,
     layer: tiled::Layer,
     layer_idx: usize,
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    tilemap_asset: &TiledMapAsset,
-    tiled_components: &mut Res<TiledComponentResource>,
-    tile_storage: &mut TileStorage,
+    context: &TiledErrorContext,
 ) -> Entity {
-    for (k, v) in &layer.properties {
-        if k == "bevy_ecs_tilemap" {
-            if let tiled::PropertyValue::BoolValue(needs) = v {
-                if *needs {
-                    return spawn_with_bevy_ecs_tilemap(
-                        commands,
-                        &layer,
-                        layer_idx,
-                        tilemap_asset,
-                        tile_storage,
-                    );
-                }
-            }
+    let (mut layer_settings, errors) =
+        ctx.settings.layer_settings(&layer, layer_idx, context);
+    for error in errors {
+        ctx.report(error);
+    }
+    ctx.spawn_settings
+        .apply(&mut layer_settings, &ctx.tilemap_asset.map);
+    // Handlers registered for the layer class
+    let handlers = ctx.layer_handlers;
+    let layer_handlers = handlers.for_layer(&layer).collect::<Vec<_>>();
+    for handler in layer_handlers.iter() {
+        handler.layer_settings(&layer, &mut layer_settings);
+    }
+
+    let layer_entity = spawn_layer_with_renderer(
+        ctx,
+        &layer,
+        layer_idx,
+        &layer_settings,
+        context,
+    );
+    ctx.commands.entity(layer_entity).insert(TiledLayer {
+        map: ctx.map_entity,
+        index: layer_idx,
+    });
+    // Tile layers are stored by renderers, make them accessible by name.
+    if ctx.tile_storage.layer_index(layer_idx).is_ok() {
+        ctx.tile_storage.set_layer_name(layer_idx, &layer.name);
+    }
+    for handler in layer_handlers {
+        handler.insert_self_to_layer(ctx, layer_entity, &layer, context);
+    }
+    layer_entity
+}
+
+fn spawn_layer_with_renderer(
+    ctx: &mut MapSpawnContext,
+    layer: &tiled::Layer,
+    layer_idx: usize,
+    layer_settings: &TiledLayerSettings,
+    context: &TiledErrorContext,
+) -> Entity {
+    // Without sprites there is nothing to render, tiles are spawned only with
+    // transforms.
+    if !layer_settings.spawn_sprites {
+        return SpritesRenderer.spawn_layer(
+            ctx,
+            layer,
+            layer_idx,
+            layer_settings,
+            context,
+        );
+    }
+    let renderers = ctx.renderers;
+    match renderers.get_for_layer(layer, layer_settings) {
+        Some(renderer) => {
+            renderer.spawn_layer(ctx, layer, layer_idx, layer_settings, context)
+        }
+        None => {
+            ctx.report(TiledMapError::UnknownRenderer {
+                context: context.clone(),
+                renderer: layer_settings.renderer.name().to_string(),
+            });
+            SpritesRenderer.spawn_layer(
+                ctx,
+                layer,
+                layer_idx,
+                layer_settings,
+                context,
+            )
         }
     }
-    let layer_entity = commands
-        .spawn((SpatialBundle {
-            transform: Transform::from_xyz(0., 0., layer_idx as f32),
-            ..default()
-        },))
-        .id();
+}
+
+pub(crate) fn spawn_with_sprites(
+    ctx: &mut MapSpawnContext,
+    layer: &tiled::Layer,
+    layer_idx: usize,
+    layer_settings: &TiledLayerSettings,
+    context: &TiledErrorContext,
+) -> Entity {
+    let tilemap_asset = ctx.tilemap_asset;
+    let layer_transform = layer_settings
+        .layer_transform(Vec2::new(layer.offset_x, -layer.offset_y));
+    #[cfg(feature = "render")]
+    let layer_bundle = SpatialBundle {
+        transform: layer_transform,
+        ..default()
+    };
+    #[cfg(not(feature = "render"))]
+    let layer_bundle = TransformBundle::from_transform(layer_transform);
+    let layer_entity = ctx.commands.spawn(layer_bundle).id();
     let layer_opacity = layer.opacity;
-    let offset_x = layer.offset_x;
-    let offset_y = layer.offset_y;
+    let map_pixel_height =
+        (tilemap_asset.map.height * tilemap_asset.map.tile_height) as f32;
     match layer.layer_type() {
         tiled::LayerType::Tiles(layer) => {
             match layer {
                 tiled::TileLayer::Infinite(_) => {
-                    panic!("Infinite layers unsupported!")
+                    ctx.report(TiledMapError::InfiniteLayer {
+                        context: context.clone(),
+                    })
                 }
                 tiled::TileLayer::Finite(layer) => {
                     let map_width = layer.width() as i32;
                     let map_height = layer.height() as i32;
                     let tile_width = tilemap_asset.map.tile_width as i32;
                     let tile_height = tilemap_asset.map.tile_height as i32;
-                    match tile_storage.init_place(
-                        layer_idx,
-                        UVec2::new(layer.width(), layer.height()),
-                    ) {
-                        Ok(_) => {}
-                        Err(e) => {
-                            error!("Error: {}", e);
-                        }
+                    let layer_size = UVec2::new(layer.width(), layer.height());
+                    if let Err(e) =
+                        ctx.tile_storage.init_place(layer_idx, layer_size)
+                    {
+                        ctx.report(TiledMapError::TileStorage {
+                            context: context.clone(),
+                            error: e,
+                        });
                     }
 
-                    match tilemap_asset.map.orientation {
-                        tiled::Orientation::Orthogonal => {
-                            for x in 0..map_width {
-                                for y in 0..map_height {
-                                    let layer_tile = match layer.get_tile(x, y)
-                                    {
-                                        Some(t) => t,
-                                        None => {
-                                            // Skip empty tile
-                                            continue;
-                                        }
-                                    };
-                                    // Transform TMX coords into bevy coords.
-                                    let mapped_y =
-                                        tilemap_asset.map.height - 1 - y as u32;
-                                    let mapped_x = x;
-                                    let mapped_y = mapped_y as i32;
-
-                                    let tls_idx = layer_tile.tileset_index();
-                                    let layer_tile_data =
-                                        match layer.get_tile_data(x, y) {
-                                            Some(t) => t,
-                                            None => continue,
-                                        };
-                                    let tile = match layer_tile.get_tile() {
-                                        Some(t) => t,
-                                        None => continue,
-                                    };
-                                    let texture_atlas = match tilemap_asset
-                                        .atlases
-                                        .get(&tls_idx)
-                                    {
-                                        Some(t) => t.clone(),
-                                        None => {
-                                            error!("There are no atlas for tilemap with index {}", tls_idx);
-                                            continue;
-                                        }
-                                    };
-
-                                    // Spawn tile
-                                    let mut tile_entity_commands = commands
-                                        .spawn(SpriteSheetBundle {
-                                            transform: Transform::from_xyz(
-                                                (mapped_x * tile_width) as f32
-                                                    + tile_width as f32 * 0.5,
-                                                (mapped_y * tile_height) as f32
-                                                    + tile_height as f32 * 0.5,
-                                                1.,
-                                            ) * Transform::from_xyz(
-                                                offset_x, -offset_y, 0.0,
-                                            ),
-                                            sprite: TextureAtlasSprite {
-                                                index: layer_tile_data.id()
-                                                    as usize,
-                                                flip_x: layer_tile_data.flip_h,
-                                                flip_y: layer_tile_data.flip_v,
-                                                color: Color::WHITE
-                                                    .with_a(layer_opacity),
-                                                ..default()
-                                            },
-                                            texture_atlas,
-                                            ..default()
-                                        });
-
-                                    spawn_tiled_components(
-                                        &tile,
-                                        tiled_components,
-                                        &mut tile_entity_commands,
-                                        asset_server,
-                                    );
-
-                                    let tile_entity = tile_entity_commands.id();
-
-                                    add_animation_if_needed(
-                                        &tile,
-                                        tilemap_asset,
-                                        &tls_idx,
-                                        commands,
-                                        tile_entity,
-                                    );
-
-                                    add_rigidbodies_if_needed(
-                                        &tile,
-                                        commands,
-                                        tile_entity,
-                                        tile_width as f32,
-                                        tile_height as f32,
-                                    );
-
-                                    commands
-                                        .entity(layer_entity)
-                                        .add_child(tile_entity);
-
-                                    // INSPECT: Tiled x and y or bevy-mapped?
-                                    // Leave Tiled for now
-                                    match tile_storage.set(
-                                        layer_idx,
-                                        &TilePos::new(x as u32, y as u32),
-                                        tile_entity,
-                                    ) {
-                                        Ok(_) => {}
-                                        Err(e) => {
-                                            error!("Error: {}", e);
-                                        }
-                                    }
+                    for x in 0..map_width {
+                        for y in 0..map_height {
+                            let layer_tile = match layer.get_tile(x, y) {
+                                Some(t) => t,
+                                None => {
+                                    // Skip empty tile
+                                    continue;
                                 }
+                            };
+                            let tile_pos = TilePos::from_tiled(
+                                UVec2::new(x as u32, y as u32),
+                                layer_size,
+                            );
+
+                            let tls_idx = layer_tile.tileset_index();
+                            let layer_tile_data =
+                                match layer.get_tile_data(x, y) {
+                                    Some(t) => t,
+                                    None => continue,
+                                };
+                            let tile = match layer_tile.get_tile() {
+                                Some(t) => t,
+                                None => continue,
+                            };
+                            let tile_context =
+                                context.with_tile(tls_idx, layer_tile.id());
+
+                            // Spawn tile
+                            let center = tile_pos.to_world(Vec2::new(
+                                tile_width as f32,
+                                tile_height as f32,
+                            ));
+                            let transform = Transform::from_xyz(
+                                center.x,
+                                center.y,
+                                layer_settings.sprite_z(
+                                    center.y - tile_height as f32 * 0.5,
+                                    map_pixel_height,
+                                ),
+                            );
+                            let Some(tile_entity) = spawn_tile_entity(
+                                ctx,
+                                layer_settings,
+                                transform,
+                                layer_tile_data,
+                                tls_idx,
+                                layer_opacity,
+                                &tile_context,
+                            ) else {
+                                continue;
+                            };
+                            ctx.commands.entity(tile_entity).insert(tile_pos);
+
+                            let tile_properties = TiledProperties::new(
+                                tile.user_type.as_deref(),
+                                &tile.properties,
+                            )
+                            .with_defaults(ctx.project)
+                            .with_files(&ctx.tilemap_asset.files);
+                            spawn_tiled_components(
+                                ctx,
+                                tile_entity,
+                                &[tile
+                                    .user_type
+                                    .as_deref()
+                                    .unwrap_or_default()],
+                                tile_properties,
+                                ComponentSource {
+                                    tile_pos: Some(tile_pos),
+                                    tileset: Some(layer_tile.get_tileset()),
+                                    object_shape: None,
+                                },
+                                &tile_context,
+                            );
+
+                            #[cfg(feature = "render")]
+                            if layer_settings.spawn_sprites {
+                                add_animation_if_needed(
+                                    &tile,
+                                    tilemap_asset,
+                                    &tls_idx,
+                                    ctx.commands,
+                                    tile_entity,
+                                );
+                            }
+
+                            #[cfg(feature = "rapier2d")]
+                            if layer_settings.spawn_colliders {
+                                add_rigidbodies_if_needed(
+                                    ctx,
+                                    &tile,
+                                    tile_entity,
+                                    tile_width as f32,
+                                    tile_height as f32,
+                                    &tile_context,
+                                );
+                            }
+
+                            ctx.commands
+                                .entity(layer_entity)
+                                .add_child(tile_entity);
+
+                            if let Err(e) = ctx.tile_storage.set(
+                                layer_idx,
+                                &tile_pos,
+                                tile_entity,
+                            ) {
+                                ctx.report(TiledMapError::TileStorage {
+                                    context: tile_context,
+                                    error: e,
+                                });
                             }
-                        }
-                        _ => {
-                            panic!("Only orthogonal maps supported!");
                         }
                     }
                 }
@@ -626,153 +1159,458 @@ fn spawn_layer(
         }
         tiled::LayerType::Objects(layer) => {
             for obj in layer.objects() {
-                let Some(tile_data) = obj.tile_data() else {
-                    warn!("No tile data for obj {:?}", obj);
-                    continue;
+                let obj_context = context.with_object(obj.id());
+                let obj_entity = if obj.tile_data().is_some() {
+                    spawn_tile_object(
+                        ctx,
+                        &obj,
+                        layer_settings,
+                        layer_opacity,
+                        map_pixel_height,
+                        &obj_context,
+                    )
+                } else {
+                    Some(spawn_shape_object(
+                        ctx,
+                        &obj,
+                        layer_settings,
+                        map_pixel_height,
+                        &obj_context,
+                    ))
                 };
-                let Some(tile) = obj.get_tile() else {
-                    warn!("No tile for obj {:?}", obj);
+                let Some(obj_entity) = obj_entity else {
                     continue;
                 };
-                let tls_idx = match tile_data.tileset_location() {
-                    tiled::TilesetLocation::Map(idx) => idx,
-                    tiled::TilesetLocation::Template(_) => {
-                        error!("Tileset for object was from Template!");
-                        continue;
-                    }
-                };
-                let texture_atlas = match tilemap_asset.atlases.get(tls_idx) {
-                    Some(t) => t.clone(),
-                    None => {
-                        error!(
-                            "There are no atlas for tilemap with index {}",
-                            tls_idx
-                        );
-                        continue;
-                    }
-                };
 
-                let obj_width = if let Some(tile) = tile.get_tile() {
-                    if let Some(ref image) = tile.image {
-                        image.width as f32
-                    } else {
-                        tile.tileset().tile_width as f32
-                    }
-                } else {
-                    tile.get_tileset().tile_width as f32
-                };
-
-                let obj_height = if let Some(tile) = tile.get_tile() {
-                    if let Some(ref image) = tile.image {
-                        image.height as f32
-                    } else {
-                        tile.tileset().tile_height as f32
-                    }
-                } else {
-                    tile.get_tileset().tile_height as f32
-                };
+                ctx.commands.entity(layer_entity).add_child(obj_entity);
+                ctx.commands.entity(obj_entity).insert(TiledObject {
+                    map: ctx.map_entity,
+                    id: obj.id(),
+                });
+                ctx.object_storage.set(
+                    obj.id(),
+                    obj_entity,
+                    layer_idx,
+                    &obj.name,
+                    &obj.user_type,
+                );
+                collect_object_refs(
+                    ctx,
+                    obj_entity,
+                    "",
+                    &obj.properties,
+                    &obj_context,
+                );
+                ctx.spawned_objects.push(TiledObjectSpawned {
+                    map: ctx.map_entity,
+                    handle: ctx.map_handle.clone_weak(),
+                    layer: layer_entity,
+                    object: obj_entity,
+                    id: obj.id(),
+                    name: obj.name.clone(),
+                    class: obj.user_type.clone(),
+                });
+            }
+        }
+        tiled::LayerType::Image(_) => {
+            ctx.report(TiledMapError::UnsupportedLayerType {
+                context: context.clone(),
+                layer_type: "Image",
+            })
+        }
+        tiled::LayerType::Group(_) => {
+            ctx.report(TiledMapError::UnsupportedLayerType {
+                context: context.clone(),
+                layer_type: "Group",
+            })
+        }
+    };
+    layer_entity
+}
 
-                let map_height = (tilemap_asset.map.height
-                    * tilemap_asset.map.tile_height)
-                    as f32;
-
-                let mapped_x = obj.x + obj_width * 0.5;
-                let mapped_y = map_height - obj.y + obj_height * 0.5;
-
-                // Spawn object
-                let mut obj_entity_commands =
-                    commands.spawn(SpriteSheetBundle {
-                        transform: Transform::from_xyz(mapped_x, mapped_y, 1.),
-                        sprite: TextureAtlasSprite {
-                            index: tile.id() as usize,
-                            flip_x: tile.flip_h,
-                            flip_y: tile.flip_v,
-                            color: Color::WHITE.with_a(layer_opacity),
-                            ..default()
-                        },
-                        texture_atlas,
-                        ..default()
-                    });
+/// Spawns an object with a tile, returns `None` if it can't be spawned.
+fn spawn_tile_object(
+    ctx: &mut MapSpawnContext,
+    obj: &tiled::Object,
+    layer_settings: &TiledLayerSettings,
+    layer_opacity: f32,
+    map_pixel_height: f32,
+    obj_context: &TiledErrorContext,
+) -> Option<Entity> {
+    let tile_data = obj.tile_data()?;
+    let Some(tile) = obj.get_tile() else {
+        warn!("No tile for obj {:?}", obj);
+        return None;
+    };
+    let tls_idx = match tile_data.tileset_location() {
+        tiled::TilesetLocation::Map(idx) => idx,
+        tiled::TilesetLocation::Template(_) => {
+            ctx.report(TiledMapError::TemplateTileset {
+                context: obj_context.clone(),
+            });
+            return None;
+        }
+    };
+    let obj_context = obj_context.with_tile(*tls_idx, tile.id());
 
-                let obj_entity = obj_entity_commands.id();
+    let obj_width = if let Some(tile) = tile.get_tile() {
+        if let Some(ref image) = tile.image {
+            image.width as f32
+        } else {
+            tile.tileset().tile_width as f32
+        }
+    } else {
+        tile.get_tileset().tile_width as f32
+    };
 
-                if let Some(tile) = obj.get_tile() {
-                    if let Some(ref tile) = tile.get_tile() {
-                        // Handle custom components
-                        spawn_tiled_components(
-                            &tile,
-                            tiled_components,
-                            &mut obj_entity_commands,
-                            asset_server,
-                        );
-                        // Handle animation
-                        add_animation_if_needed(
-                            tile,
-                            tilemap_asset,
-                            tls_idx,
-                            commands,
-                            obj_entity,
-                        );
-                        // Handle collision
-                        add_rigidbodies_if_needed(
-                            tile, commands, obj_entity, obj_width, obj_height,
-                        );
-                    }
-                };
+    let obj_height = if let Some(tile) = tile.get_tile() {
+        if let Some(ref image) = tile.image {
+            image.height as f32
+        } else {
+            tile.tileset().tile_height as f32
+        }
+    } else {
+        tile.get_tileset().tile_height as f32
+    };
 
-                commands.entity(layer_entity).add_child(obj_entity);
+    let mapped_x = obj.x + obj_width * 0.5;
+    let mapped_y = map_pixel_height - obj.y + obj_height * 0.5;
+    let transform = Transform::from_xyz(
+        mapped_x,
+        mapped_y,
+        layer_settings.sprite_z(mapped_y - obj_height * 0.5, map_pixel_height),
+    );
+
+    // Spawn object
+    let obj_entity = spawn_tile_entity(
+        ctx,
+        layer_settings,
+        transform,
+        &tile_data,
+        *tls_idx,
+        layer_opacity,
+        &obj_context,
+    )?;
+
+    // Object class and properties override ones from the tile.
+    let tile_class = tile
+        .get_tile()
+        .and_then(|tile| tile.user_type.clone())
+        .unwrap_or_default();
+    let class = match obj.user_type.as_str() {
+        "" => tile_class.as_str(),
+        class => class,
+    };
+    let properties = TiledProperties {
+        properties: match tile.get_tile() {
+            Some(tile) => merge_properties(&tile.properties, &obj.properties),
+            None => {
+                merge_properties(&tiled::Properties::new(), &obj.properties)
+            }
+        },
+        ..TiledProperties::new(Some(class), &tiled::Properties::new())
+    }
+    .with_defaults(ctx.project)
+    .with_files(&ctx.tilemap_asset.files);
+    spawn_tiled_components(
+        ctx,
+        obj_entity,
+        &[obj.user_type.as_str(), tile_class.as_str()],
+        properties,
+        ComponentSource {
+            tile_pos: None,
+            tileset: Some(tile.get_tileset()),
+            object_shape: Some(&obj.shape),
+        },
+        &obj_context,
+    );
+
+    if let Some(tile) = obj.get_tile() {
+        if let Some(ref tile) = tile.get_tile() {
+            // Handle animation
+            #[cfg(feature = "render")]
+            if layer_settings.spawn_sprites {
+                add_animation_if_needed(
+                    tile,
+                    ctx.tilemap_asset,
+                    tls_idx,
+                    ctx.commands,
+                    obj_entity,
+                );
+            }
+            // Handle collision
+            #[cfg(feature = "rapier2d")]
+            if layer_settings.spawn_colliders {
+                add_rigidbodies_if_needed(
+                    ctx,
+                    tile,
+                    obj_entity,
+                    obj_width,
+                    obj_height,
+                    &obj_context,
+                );
             }
         }
-        tiled::LayerType::Image(layer) => {
-            // Spawn image layer
-            todo!()
-        }
-        tiled::LayerType::Group(layer) => {
-            // Spawn group layer
-            todo!()
+    }
+    Some(obj_entity)
+}
+
+/// Spawns an object without a tile at the center of its shape, it gets only
+/// a transform and custom components.
+fn spawn_shape_object(
+    ctx: &mut MapSpawnContext,
+    obj: &tiled::Object,
+    layer_settings: &TiledLayerSettings,
+    map_pixel_height: f32,
+    obj_context: &TiledErrorContext,
+) -> Entity {
+    let (mapped_x, mapped_y) = match obj.shape {
+        tiled::ObjectShape::Rect { width, height }
+        | tiled::ObjectShape::Ellipse { width, height } => {
+            (obj.x + width * 0.5, map_pixel_height - obj.y - height * 0.5)
         }
+        _ => (obj.x, map_pixel_height - obj.y),
     };
-    layer_entity
+    let transform = Transform::from_xyz(
+        mapped_x,
+        mapped_y,
+        layer_settings.sprite_z(mapped_y, map_pixel_height),
+    );
+    let obj_entity = ctx
+        .commands
+        .spawn(TransformBundle::from_transform(transform))
+        .id();
+    let properties =
+        TiledProperties::new(Some(&obj.user_type), &obj.properties)
+            .with_defaults(ctx.project)
+            .with_files(&ctx.tilemap_asset.files);
+    spawn_tiled_components(
+        ctx,
+        obj_entity,
+        &[obj.user_type.as_str()],
+        properties,
+        ComponentSource {
+            object_shape: Some(&obj.shape),
+            ..default()
+        },
+        obj_context,
+    );
+    obj_entity
+}
+
+/// Spawns the entity of a tile or a tile object, with a sprite if the layer
+/// spawns sprites. Returns `None` if the sprite can't be created.
+#[cfg_attr(not(feature = "render"), allow(unused_variables))]
+fn spawn_tile_entity(
+    ctx: &mut MapSpawnContext,
+    layer_settings: &TiledLayerSettings,
+    transform: Transform,
+    tile_data: &tiled::LayerTileData,
+    tileset_idx: usize,
+    opacity: f32,
+    context: &TiledErrorContext,
+) -> Option<Entity> {
+    #[cfg(feature = "render")]
+    if layer_settings.spawn_sprites {
+        let tilemap_asset = ctx.tilemap_asset;
+        let Some(texture_atlas) = tilemap_asset.atlases.get(&tileset_idx)
+        else {
+            ctx.report(TiledMapError::MissingAtlas {
+                context: context.clone(),
+                tileset_idx,
+            });
+            return None;
+        };
+        let sprite = SpriteSheetBundle {
+            transform,
+            sprite: TextureAtlasSprite {
+                index: tile_data.id() as usize,
+                flip_x: tile_data.flip_h,
+                flip_y: tile_data.flip_v,
+                color: Color::WHITE.with_a(opacity),
+                ..default()
+            },
+            texture_atlas: texture_atlas.clone(),
+            ..default()
+        };
+        return Some(ctx.commands.spawn(sprite).id());
+    }
+    Some(
+        ctx.commands
+            .spawn(TransformBundle::from_transform(transform))
+            .id(),
+    )
 }
 
+/// Tile or object, which components are inserted to.
+#[derive(Default)]
+struct ComponentSource<'a> {
+    tile_pos: Option<TilePos>,
+    tileset: Option<&'a tiled::Tileset>,
+    object_shape: Option<&'a tiled::ObjectShape>,
+}
+
+/// Insert registered `TiledComponent`s and `TiledComponentFactory`s, which
+/// class is one of `classes`. Classes without them are inserted as reflected
+/// components, if there are such types in `AppTypeRegistry`.
 fn spawn_tiled_components(
-    tile: &tiled::Tile,
-    tiled_components: &mut Res<TiledComponentResource>,
-    tile_entity_commands: &mut bevy::ecs::system::EntityCommands,
-    asset_server: &Res<AssetServer>,
+    ctx: &mut MapSpawnContext,
+    entity: Entity,
+    classes: &[&str],
+    properties: TiledProperties,
+    source: ComponentSource,
+    context: &TiledErrorContext,
 ) {
-    let properties: HashMap<String, tiled::PropertyValue> = tile
-        .properties
-        .iter()
-        .map(|(k, v)| (k.clone(), v.clone()))
-        .collect();
-
-    for comp in &tiled_components.vec {
-        if let Some(ref class) = tile.user_type {
-            if comp.get_class_name() == class {
-                comp.insert_self_to_entity(
-                    tile_entity_commands,
-                    properties.clone(),
-                    asset_server,
-                );
+    let tiled_components = ctx.tiled_components;
+    let asset_server = ctx.asset_server;
+    let mut reflected_classes = Vec::new();
+    for (idx, class) in classes.iter().enumerate() {
+        // Skip empty and repeated classes
+        if class.is_empty() || classes[..idx].contains(class) {
+            continue;
+        }
+        let mut registered = false;
+        for comp in tiled_components
+            .vec
+            .iter()
+            .filter(|comp| comp.get_class_name() == *class)
+        {
+            if let Err(e) = comp.try_insert_self_to_entity(
+                &mut ctx.commands.entity(entity),
+                properties.properties.clone(),
+                asset_server,
+            ) {
+                ctx.report(TiledMapError::ComponentFailed {
+                    context: context.clone(),
+                    class: class.to_string(),
+                    reason: e.to_string(),
+                });
             }
+            registered = true;
         }
+
+        let component_context = TiledComponentContext {
+            class,
+            properties: &properties,
+            asset_server,
+            location: context,
+            tile_pos: source.tile_pos,
+            tileset: source.tileset,
+            object_shape: source.object_shape,
+            project: ctx.project,
+        };
+        for (_, factory) in tiled_components
+            .factories
+            .iter()
+            .filter(|(factory_class, _)| factory_class == class)
+        {
+            if let Err(e) = factory.insert_to_entity(
+                &mut ctx.commands.entity(entity),
+                &component_context,
+            ) {
+                ctx.report(TiledMapError::ComponentFailed {
+                    context: context.clone(),
+                    class: class.to_string(),
+                    reason: format!("{:#}", e),
+                });
+            }
+            registered = true;
+        }
+
+        if !registered {
+            reflected_classes.push(class.to_string());
+        }
+    }
+
+    if !reflected_classes.is_empty() {
+        ctx.commands.add(InsertReflectedComponents {
+            entity,
+            classes: reflected_classes,
+            properties: properties.properties,
+            context: context.clone(),
+        });
     }
 }
 
+/// Remembers object properties of the object, including members of class
+/// properties, to resolve them when all objects are spawned.
+fn collect_object_refs(
+    ctx: &mut MapSpawnContext,
+    entity: Entity,
+    prefix: &str,
+    properties: &tiled::Properties,
+    context: &TiledErrorContext,
+) {
+    for (name, value) in properties.iter() {
+        let property = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", prefix, name)
+        };
+        match value {
+            // Zero id means the property is not set
+            tiled::PropertyValue::ObjectValue(0) => {}
+            tiled::PropertyValue::ObjectValue(target) => {
+                ctx.object_refs.push(PendingObjectRef {
+                    entity,
+                    property,
+                    target: *target,
+                    context: context.clone(),
+                })
+            }
+            tiled::PropertyValue::ClassValue { properties, .. } => {
+                collect_object_refs(ctx, entity, &property, properties, context)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Inserts `TiledObjectRefs` to objects, which reference other objects, and
+/// `TiledObjectRef` if there is a single reference.
+fn resolve_object_refs(ctx: &mut MapSpawnContext) {
+    let mut refs: HashMap<Entity, TiledObjectRefs> = HashMap::new();
+    for pending in std::mem::take(&mut ctx.object_refs) {
+        let Some(target) = ctx.object_storage.get(pending.target) else {
+            ctx.report(TiledMapError::MissingObjectRef {
+                context: pending.context,
+                property: pending.property,
+                target: pending.target,
+            });
+            continue;
+        };
+        refs.entry(pending.entity)
+            .or_default()
+            .refs
+            .insert(pending.property, target);
+    }
+
+    for (entity, object_refs) in refs {
+        let mut entity_commands = ctx.commands.entity(entity);
+        if object_refs.refs.len() == 1 {
+            if let Some(target) = object_refs.refs.values().next() {
+                entity_commands.insert(TiledObjectRef(*target));
+            }
+        }
+        entity_commands.insert(object_refs);
+    }
+}
+
+#[cfg(feature = "rapier2d")]
 fn add_rigidbodies_if_needed(
+    ctx: &mut MapSpawnContext,
     tile: &tiled::Tile,
-    commands: &mut Commands,
     entity: Entity,
     container_width: f32,
     container_height: f32,
+    context: &TiledErrorContext,
 ) {
     if let Some(ref obj_layer_data) = tile.collision {
         for data in obj_layer_data.object_data() {
             use tiled::ObjectShape;
             match &data.shape {
                 ObjectShape::Rect { width, height } => {
-                    commands
+                    ctx.commands
                         .entity(entity)
                         .insert(RigidBody::Fixed)
                         .with_children(|parent| {
@@ -795,11 +1633,11 @@ fn add_rigidbodies_if_needed(
                 }
                 ObjectShape::Ellipse { width, height } => {
                     if width != height {
-                        error!(
+                        warn!(
                             "Only ball colliders supported! Spawning ball instead of ellipse."
                         );
                     }
-                    commands
+                    ctx.commands
                         .entity(entity)
                         .insert(RigidBody::Fixed)
                         .with_children(|parent| {
@@ -825,12 +1663,17 @@ fn add_rigidbodies_if_needed(
                         .iter()
                         .map(|(x, y)| Vec2::new(*x, *y * -1.))
                         .collect::<Vec<Vec2>>();
-                    let collider = Collider::convex_hull(&points).unwrap();
+                    let Some(collider) = Collider::convex_hull(&points) else {
+                        ctx.report(TiledMapError::InvalidCollider {
+                            context: context.clone(),
+                        });
+                        continue;
+                    };
                     let mapped_x_zero = container_width / 2.;
                     let x_tiled_to_bevy = (mapped_x_zero - data.x) * -1.;
                     let mapped_y_zero = container_height / 2.;
                     let y_tiled_to_bevy = mapped_y_zero - data.y;
-                    commands
+                    ctx.commands
                         .entity(entity)
                         .insert(RigidBody::Fixed)
                         .with_children(|parent| {
@@ -844,14 +1687,16 @@ fn add_rigidbodies_if_needed(
                             ));
                         });
                 }
-                _ => {
-                    panic!("Not implemented");
-                }
+                shape => ctx.report(TiledMapError::UnsupportedCollisionShape {
+                    context: context.clone(),
+                    shape: format!("{:?}", shape),
+                }),
             }
         }
     }
 }
 
+#[cfg(feature = "render")]
 fn add_animation_if_needed(
     tile: &tiled::Tile,
     tilemap_asset: &TiledMapAsset,
@@ -883,6 +1728,7 @@ fn add_animation_if_needed(
     }
 }
 
+#[cfg(feature = "render")]
 fn system_animate_entities(
     mut query: Query<(&mut Animation, &mut TextureAtlasSprite)>,
     time: Res<Time>,
@@ -936,7 +1782,24 @@ fn events_to_vectors(
     changed_maps
 }
 
-fn tiled_color_to_bevy(color: &tiled::Color) -> Color {
+/// Merge Tiled properties, `overrides` replace values from `base`.
+fn merge_properties(
+    base: &tiled::Properties,
+    overrides: &tiled::Properties,
+) -> HashMap<String, tiled::PropertyValue> {
+    base.iter()
+        .chain(overrides.iter())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+fn report_error(errors: &mut EventWriter<TiledMapError>, error: TiledMapError) {
+    log::error!("{}", error);
+    errors.send(error);
+}
+
+#[cfg(feature = "render")]
+pub(crate) fn tiled_color_to_bevy(color: &tiled::Color) -> Color {
     let red = color.red as f32 / 255.;
     let green = color.green as f32 / 255.;
     let blue = color.blue as f32 / 255.;
@@ -949,6 +1812,7 @@ fn tiled_color_to_bevy(color: &tiled::Color) -> Color {
     }
 }
 
+#[cfg(feature = "render")]
 fn inc_frame(cur: u32, max: u32) -> u32 {
     if cur >= max {
         0
@@ -996,3 +1860,93 @@ fn get_rect_from_convex(vec: &[Vec2]) -> (f32, f32) {
 
     (x_max - x_min, y_max - y_min)
 }
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn initialize_app() -> App {
+        let mut app = App::new();
+        app.add_event::<TiledMapDespawned>()
+            .add_event::<TiledLayerDespawned>()
+            .add_event::<TiledObjectDespawned>()
+            .add_systems(
+                Update,
+                (system_despawn_maps, system_forget_despawned_objects),
+            );
+        app
+    }
+
+    /// Spawns a map with one layer and one object, returns entities of the
+    /// map, the layer and the object.
+    fn spawn_map(app: &mut App) -> (Entity, Entity, Entity) {
+        let map = app.world.spawn_empty().id();
+        let layer = app.world.spawn_empty().id();
+        let object = app.world.spawn(TiledObject { map, id: 3 }).id();
+        let mut layer_storage = LayerStorage::new();
+        layer_storage.set(0, layer, 5, "Doors", Some("Interactive"));
+        let mut object_storage = ObjectStorage::new();
+        object_storage.set(3, object, 0, "front door", "Door");
+        app.world.entity_mut(map).insert((
+            Handle::<TiledMapAsset>::default(),
+            layer_storage,
+            object_storage,
+        ));
+        (map, layer, object)
+    }
+
+    #[test]
+    fn test_despawn_events() {
+        let mut app = initialize_app();
+        let (map, layer, object) = spawn_map(&mut app);
+        app.world.entity_mut(map).insert(DespawnTiledMap);
+        app.update();
+
+        let layer_events = app.world.resource::<Events<TiledLayerDespawned>>();
+        let events: Vec<_> =
+            layer_events.get_reader().iter(layer_events).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].map, map);
+        assert_eq!(events[0].layer, layer);
+        assert_eq!(events[0].id, 5);
+        assert_eq!(events[0].name, "Doors");
+        assert_eq!(events[0].class.as_deref(), Some("Interactive"));
+
+        let object_events =
+            app.world.resource::<Events<TiledObjectDespawned>>();
+        let events: Vec<_> =
+            object_events.get_reader().iter(object_events).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].object, object);
+        assert_eq!(events[0].id, 3);
+        assert_eq!(events[0].name, "front door");
+        assert_eq!(events[0].class, "Door");
+
+        let map_events = app.world.resource::<Events<TiledMapDespawned>>();
+        assert_eq!(map_events.get_reader().iter(map_events).count(), 1);
+        assert!(app.world.get_entity(map).is_none());
+        assert!(app.world.get_entity(layer).is_none());
+    }
+
+    #[test]
+    fn test_forget_despawned_object() {
+        let mut app = initialize_app();
+        let (map, _, object) = spawn_map(&mut app);
+        app.world.despawn(object);
+        app.update();
+
+        let object_events =
+            app.world.resource::<Events<TiledObjectDespawned>>();
+        let events: Vec<_> =
+            object_events.get_reader().iter(object_events).collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].map, map);
+        assert_eq!(events[0].id, 3);
+        assert_eq!(events[0].name, "front door");
+        assert_eq!(events[0].class, "Door");
+        let object_storage = app.world.get::<ObjectStorage>(map).unwrap();
+        assert!(object_storage.is_empty());
+    }
+}
@@ -0,0 +1,579 @@
+//! Custom property types from `.tiled-project` files.
+
+use bevy::asset::{AssetLoader, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::HashMap;
+use serde::Deserialize;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Custom classes and enums of a Tiled project. Set
+/// [TiledToolkitSettings::project](crate::settings::TiledToolkitSettings::project)
+/// to merge class defaults into properties passed to components.
+#[derive(TypeUuid, TypePath, Default, Debug)]
+#[uuid = "3c1b5a0e-7d2f-4c8e-9a61-0f4b2d8e6a17"]
+pub struct TiledProjectAsset {
+    /// Custom property types by name.
+    pub property_types: HashMap<String, TiledPropertyType>,
+}
+
+/// Custom property type declared in the project.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TiledPropertyType {
+    Class(TiledClassType),
+    Enum(TiledEnumType),
+}
+
+/// Custom class with default values of its members.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledClassType {
+    pub name: String,
+    /// Members with their default values.
+    pub members: Vec<TiledClassMember>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledClassMember {
+    pub name: String,
+    /// Name of the class or enum type of the member.
+    pub property_type: Option<String>,
+    pub default: tiled::PropertyValue,
+}
+
+/// Custom enum, its values are stored as strings or ints in maps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TiledEnumType {
+    pub name: String,
+    pub values: Vec<String>,
+    /// Several values can be set at once.
+    pub values_as_flags: bool,
+}
+
+/// Typed value of an enum property.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TiledEnumValue {
+    Single(String),
+    Flags(Vec<String>),
+}
+
+impl TiledProjectAsset {
+    /// Returns the class type by name.
+    pub fn class(&self, name: &str) -> Option<&TiledClassType> {
+        match self.property_types.get(name) {
+            Some(TiledPropertyType::Class(class)) => Some(class),
+            _ => None,
+        }
+    }
+
+    /// Returns the enum type by name.
+    pub fn enum_type(&self, name: &str) -> Option<&TiledEnumType> {
+        match self.property_types.get(name) {
+            Some(TiledPropertyType::Enum(enum_type)) => Some(enum_type),
+            _ => None,
+        }
+    }
+
+    /// Inserts default values of `class` members missing in `properties`,
+    /// and of members of nested class properties.
+    pub fn apply_defaults(
+        &self,
+        class: Option<&str>,
+        properties: &mut HashMap<String, tiled::PropertyValue>,
+    ) {
+        if let Some(class) = class.and_then(|class| self.class(class)) {
+            for member in class.members.iter() {
+                properties
+                    .entry(member.name.clone())
+                    .or_insert_with(|| member.default.clone());
+            }
+        }
+        for value in properties.values_mut() {
+            self.apply_class_value_defaults(value);
+        }
+    }
+
+    /// Returns the type name of the class member by dotted path.
+    pub fn member_type(&self, class: &str, path: &str) -> Option<&str> {
+        let mut property_type = class;
+        for segment in path.split('.') {
+            property_type = self
+                .class(property_type)?
+                .members
+                .iter()
+                .find(|member| member.name == segment)?
+                .property_type
+                .as_deref()?;
+        }
+        Some(property_type)
+    }
+
+    /// Converts the raw value of an enum property, stored as a string or an
+    /// int, into names of the enum values. Returns `None` if the value or one
+    /// of the flags isn't declared in the enum.
+    pub fn enum_value(
+        &self,
+        enum_name: &str,
+        value: &tiled::PropertyValue,
+    ) -> Option<TiledEnumValue> {
+        let enum_type = self.enum_type(enum_name)?;
+        match (value, enum_type.values_as_flags) {
+            (tiled::PropertyValue::StringValue(v), false) => enum_type
+                .values
+                .contains(v)
+                .then(|| TiledEnumValue::Single(v.clone())),
+            (tiled::PropertyValue::StringValue(v), true) => v
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(|flag| {
+                    let known =
+                        enum_type.values.iter().any(|value| value == flag);
+                    if !known {
+                        warn!("Enum {} has no value {}", enum_name, flag);
+                    }
+                    known.then(|| flag.to_string())
+                })
+                .collect::<Option<_>>()
+                .map(TiledEnumValue::Flags),
+            (tiled::PropertyValue::IntValue(v), false) => {
+                let idx = usize::try_from(*v).ok()?;
+                let value = enum_type.values.get(idx)?;
+                Some(TiledEnumValue::Single(value.clone()))
+            }
+            (tiled::PropertyValue::IntValue(v), true) => {
+                // Bits above the last value don't belong to the enum
+                let len = enum_type.values.len();
+                if len < 32 && *v >> len != 0 {
+                    warn!("Enum {} has no flags {:#b}", enum_name, v >> len);
+                    return None;
+                }
+                Some(TiledEnumValue::Flags(
+                    enum_type
+                        .values
+                        .iter()
+                        .enumerate()
+                        .filter(|(idx, _)| *idx < 32 && v & (1 << idx) != 0)
+                        .map(|(_, value)| value.clone())
+                        .collect(),
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    fn apply_class_value_defaults(&self, value: &mut tiled::PropertyValue) {
+        let tiled::PropertyValue::ClassValue {
+            property_type,
+            properties,
+        } = value
+        else {
+            return;
+        };
+        if let Some(class) = self.class(property_type) {
+            for member in class.members.iter() {
+                properties
+                    .entry(member.name.clone())
+                    .or_insert_with(|| member.default.clone());
+            }
+        }
+        for value in properties.values_mut() {
+            self.apply_class_value_defaults(value);
+        }
+    }
+
+    /// Warns about enum members, which defaults aren't values of the enum.
+    fn check_enum_defaults(&self) {
+        let classes = self.property_types.values().filter_map(|t| match t {
+            TiledPropertyType::Class(class) => Some(class),
+            TiledPropertyType::Enum(_) => None,
+        });
+        for class in classes {
+            for member in class.members.iter() {
+                let Some(enum_name) = member.property_type.as_deref() else {
+                    continue;
+                };
+                if self.enum_type(enum_name).is_some()
+                    && self.enum_value(enum_name, &member.default).is_none()
+                {
+                    warn!(
+                        "Default value of {}.{} is not a value of enum {}",
+                        class.name, member.name, enum_name
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Type for loading `.tiled-project` files with `bevy`'s `AssetLoader`.
+pub(crate) struct TiledProjectLoader;
+
+impl AssetLoader for TiledProjectLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::utils::BoxedFuture<'a, anyhow::Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let project: ProjectJson = serde_json::from_slice(bytes)
+                .map_err(|e| anyhow::anyhow!("Could not load project: {e}"))?;
+            load_context
+                .set_default_asset(LoadedAsset::new(project.into_asset()));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tiled-project"]
+    }
+}
+
+// ───── Project file format ──────────────────────────────────────────────── //
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProjectJson {
+    #[serde(default)]
+    property_types: Vec<PropertyTypeJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum PropertyTypeJson {
+    Class {
+        name: String,
+        #[serde(default)]
+        members: Vec<MemberJson>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Enum {
+        name: String,
+        #[serde(default)]
+        values: Vec<String>,
+        #[serde(default)]
+        values_as_flags: bool,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MemberJson {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    property_type: Option<String>,
+    #[serde(default)]
+    value: serde_json::Value,
+}
+
+impl ProjectJson {
+    fn into_asset(self) -> TiledProjectAsset {
+        let classes: HashMap<&str, &[MemberJson]> = self
+            .property_types
+            .iter()
+            .filter_map(|property_type| match property_type {
+                PropertyTypeJson::Class { name, members } => {
+                    Some((name.as_str(), members.as_slice()))
+                }
+                PropertyTypeJson::Enum { .. } => None,
+            })
+            .collect();
+
+        let property_types = self
+            .property_types
+            .iter()
+            .map(|property_type| match property_type {
+                PropertyTypeJson::Class { name, members } => (
+                    name.clone(),
+                    TiledPropertyType::Class(TiledClassType {
+                        name: name.clone(),
+                        members: members
+                            .iter()
+                            .filter_map(|member| {
+                                let Some(default) =
+                                    member_value(&classes, member)
+                                else {
+                                    warn!(
+                                        "Can't parse default value of {}.{}",
+                                        name, member.name
+                                    );
+                                    return None;
+                                };
+                                Some(TiledClassMember {
+                                    name: member.name.clone(),
+                                    property_type: member
+                                        .property_type
+                                        .clone()
+                                        .filter(|t| !t.is_empty()),
+                                    default,
+                                })
+                            })
+                            .collect(),
+                    }),
+                ),
+                PropertyTypeJson::Enum {
+                    name,
+                    values,
+                    values_as_flags,
+                } => (
+                    name.clone(),
+                    TiledPropertyType::Enum(TiledEnumType {
+                        name: name.clone(),
+                        values: values.clone(),
+                        values_as_flags: *values_as_flags,
+                    }),
+                ),
+            })
+            .collect();
+        let project = TiledProjectAsset { property_types };
+        project.check_enum_defaults();
+        project
+    }
+}
+
+// ───── Utility functions ────────────────────────────────────────────────── //
+
+/// Converts the JSON value of the class member into a property value, members
+/// of a class value are converted with types of the nested class members.
+fn member_value(
+    classes: &HashMap<&str, &[MemberJson]>,
+    member: &MemberJson,
+) -> Option<tiled::PropertyValue> {
+    let value = &member.value;
+    Some(match member.kind.as_str() {
+        "bool" => tiled::PropertyValue::BoolValue(value.as_bool()?),
+        "int" => {
+            tiled::PropertyValue::IntValue(i32::try_from(value.as_i64()?).ok()?)
+        }
+        "float" => tiled::PropertyValue::FloatValue(value.as_f64()? as f32),
+        "string" => {
+            tiled::PropertyValue::StringValue(value.as_str()?.to_string())
+        }
+        "file" => tiled::PropertyValue::FileValue(value.as_str()?.to_string()),
+        "object" => tiled::PropertyValue::ObjectValue(
+            u32::try_from(value.as_u64()?).ok()?,
+        ),
+        "color" => {
+            tiled::PropertyValue::ColorValue(parse_color(value.as_str()?)?)
+        }
+        "class" => {
+            let property_type = member.property_type.clone()?;
+            let nested = classes.get(property_type.as_str());
+            let properties = value
+                .as_object()
+                .map(|object| {
+                    object
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            let nested_member = nested?
+                                .iter()
+                                .find(|nested| nested.name == *name)?;
+                            let member = MemberJson {
+                                name: name.clone(),
+                                kind: nested_member.kind.clone(),
+                                property_type: nested_member
+                                    .property_type
+                                    .clone(),
+                                value: value.clone(),
+                            };
+                            let Some(value) = member_value(classes, &member)
+                            else {
+                                warn!(
+                                    "Can't parse default value of {}.{}",
+                                    property_type, name
+                                );
+                                return None;
+                            };
+                            Some((name.clone(), value))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            tiled::PropertyValue::ClassValue {
+                property_type,
+                properties,
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Parses `#AARRGGBB` or `#RRGGBB` color.
+fn parse_color(color: &str) -> Option<tiled::Color> {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    let channel =
+        |idx: usize| u8::from_str_radix(hex.get(idx..idx + 2)?, 16).ok();
+    match hex.len() {
+        8 => Some(tiled::Color {
+            alpha: channel(0)?,
+            red: channel(2)?,
+            green: channel(4)?,
+            blue: channel(6)?,
+        }),
+        6 => Some(tiled::Color {
+            alpha: 255,
+            red: channel(0)?,
+            green: channel(2)?,
+            blue: channel(4)?,
+        }),
+        _ => None,
+    }
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECT: &str = r##"{
+        "propertyTypes": [
+            {
+                "id": 1,
+                "name": "Stats",
+                "type": "class",
+                "useAs": ["property", "object"],
+                "members": [
+                    { "name": "health", "type": "int", "value": 10 },
+                    { "name": "tint", "type": "color", "value": "#ff102030" },
+                    {
+                        "name": "element",
+                        "type": "string",
+                        "propertyType": "Element",
+                        "value": "Fire"
+                    }
+                ]
+            },
+            {
+                "id": 2,
+                "name": "Ninja",
+                "type": "class",
+                "members": [
+                    {
+                        "name": "stats",
+                        "type": "class",
+                        "propertyType": "Stats",
+                        "value": { "health": 20 }
+                    }
+                ]
+            },
+            {
+                "id": 3,
+                "name": "Element",
+                "type": "enum",
+                "storageType": "string",
+                "values": ["Fire", "Water"],
+                "valuesAsFlags": false
+            },
+            {
+                "id": 4,
+                "name": "Resist",
+                "type": "enum",
+                "storageType": "int",
+                "values": ["Fire", "Water", "Wind"],
+                "valuesAsFlags": true
+            }
+        ]
+    }"##;
+
+    fn initialize_project() -> TiledProjectAsset {
+        serde_json::from_str::<ProjectJson>(PROJECT)
+            .unwrap()
+            .into_asset()
+    }
+
+    #[test]
+    fn test_apply_defaults() {
+        let project = initialize_project();
+        let mut properties = HashMap::new();
+        project.apply_defaults(Some("Ninja"), &mut properties);
+
+        let Some(tiled::PropertyValue::ClassValue { properties, .. }) =
+            properties.get("stats")
+        else {
+            panic!("stats should be a class value");
+        };
+        assert_eq!(
+            properties.get("health"),
+            Some(&tiled::PropertyValue::IntValue(20))
+        );
+        assert_eq!(
+            properties.get("element"),
+            Some(&tiled::PropertyValue::StringValue("Fire".to_string()))
+        );
+        assert_eq!(
+            properties.get("tint"),
+            Some(&tiled::PropertyValue::ColorValue(tiled::Color {
+                alpha: 255,
+                red: 16,
+                green: 32,
+                blue: 48,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_enum_value() {
+        let project = initialize_project();
+        assert_eq!(
+            project.member_type("Ninja", "stats.element"),
+            Some("Element")
+        );
+        assert_eq!(
+            project.enum_value(
+                "Element",
+                &tiled::PropertyValue::StringValue("Water".to_string())
+            ),
+            Some(TiledEnumValue::Single("Water".to_string()))
+        );
+        assert_eq!(
+            project.enum_value("Resist", &tiled::PropertyValue::IntValue(5)),
+            Some(TiledEnumValue::Flags(vec![
+                "Fire".to_string(),
+                "Wind".to_string()
+            ]))
+        );
+        // Unknown values and flags
+        assert_eq!(
+            project.enum_value(
+                "Resist",
+                &tiled::PropertyValue::StringValue("Fire,Wnid".to_string())
+            ),
+            None
+        );
+        assert_eq!(
+            project.enum_value("Resist", &tiled::PropertyValue::IntValue(9)),
+            None
+        );
+        assert_eq!(
+            project.enum_value(
+                "Element",
+                &tiled::PropertyValue::StringValue("Earth".to_string())
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bad_defaults() {
+        let project: ProjectJson = serde_json::from_str(
+            r#"{
+                "propertyTypes": [{
+                    "id": 1,
+                    "name": "Stats",
+                    "type": "class",
+                    "members": [
+                        { "name": "health", "type": "int", "value": "full" },
+                        { "name": "armor", "type": "int", "value": 5 }
+                    ]
+                }]
+            }"#,
+        )
+        .unwrap();
+        let project = project.into_asset();
+        let members = &project.class("Stats").unwrap().members;
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name, "armor");
+    }
+}
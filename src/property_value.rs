@@ -0,0 +1,252 @@
+//! Conversions from Tiled properties into Rust types, used by
+//! `#[derive(TiledComponent)]` and `#[derive(TiledClass)]`.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Errors which can be returned when reading Tiled properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TiledPropertyError {
+    /// There is no property with this name.
+    Missing { name: String },
+    /// Property has an unexpected type.
+    TypeMismatch {
+        name: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+}
+
+impl TiledPropertyError {
+    /// Create new error for the property value, which can't be converted.
+    pub fn mismatch(
+        expected: &'static str,
+        value: &tiled::PropertyValue,
+    ) -> Self {
+        TiledPropertyError::TypeMismatch {
+            name: String::new(),
+            expected,
+            found: property_type_name(value),
+        }
+    }
+
+    /// Returns the dotted path to the property.
+    pub fn name(&self) -> &str {
+        match self {
+            TiledPropertyError::Missing { name }
+            | TiledPropertyError::TypeMismatch { name, .. } => name,
+        }
+    }
+
+    /// Prepends the name of the parent property to the path.
+    pub fn with_parent(mut self, parent: &str) -> Self {
+        let (TiledPropertyError::Missing { name }
+        | TiledPropertyError::TypeMismatch { name, .. }) = &mut self;
+        *name = if name.is_empty() {
+            parent.to_string()
+        } else {
+            format!("{}.{}", parent, name)
+        };
+        self
+    }
+}
+
+impl Display for TiledPropertyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TiledPropertyError::Missing { name } => {
+                write!(f, "Property {} is missing", name)
+            }
+            TiledPropertyError::TypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Property {} should be {}, but it is {}",
+                name, expected, found
+            ),
+        }
+    }
+}
+
+impl Error for TiledPropertyError {}
+
+/// Types which can be read from a single Tiled property value.
+pub trait FromTiledProperty: Sized {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError>;
+}
+
+/// Types which can be built from all properties of a Tiled element or a
+/// class property. Implement it with `#[derive(TiledClass)]`.
+pub trait FromTiledProperties: Sized {
+    fn from_tiled_properties(
+        properties: &tiled::Properties,
+        asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError>;
+}
+
+impl FromTiledProperty for f32 {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        _asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::FloatValue(v) => Ok(*v),
+            tiled::PropertyValue::IntValue(v) => Ok(*v as f32),
+            _ => Err(TiledPropertyError::mismatch("float", value)),
+        }
+    }
+}
+
+impl FromTiledProperty for i32 {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        _asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::IntValue(v) => Ok(*v),
+            _ => Err(TiledPropertyError::mismatch("int", value)),
+        }
+    }
+}
+
+impl FromTiledProperty for bool {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        _asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::BoolValue(v) => Ok(*v),
+            _ => Err(TiledPropertyError::mismatch("bool", value)),
+        }
+    }
+}
+
+impl FromTiledProperty for String {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        _asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::StringValue(v)
+            | tiled::PropertyValue::FileValue(v) => Ok(v.clone()),
+            _ => Err(TiledPropertyError::mismatch("string", value)),
+        }
+    }
+}
+
+#[cfg(feature = "render")]
+impl FromTiledProperty for Color {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        _asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::ColorValue(v) => {
+                Ok(crate::plugin::tiled_color_to_bevy(v))
+            }
+            _ => Err(TiledPropertyError::mismatch("color", value)),
+        }
+    }
+}
+
+/// Loads the asset from a file property.
+impl<T: bevy::asset::Asset> FromTiledProperty for Handle<T> {
+    fn from_tiled_property(
+        value: &tiled::PropertyValue,
+        asset_server: &AssetServer,
+    ) -> Result<Self, TiledPropertyError> {
+        match value {
+            tiled::PropertyValue::FileValue(path) => {
+                Ok(asset_server.load(path.as_str()))
+            }
+            _ => Err(TiledPropertyError::mismatch("file", value)),
+        }
+    }
+}
+
+/// Reads the required property by name.
+pub fn get_property<T: FromTiledProperty>(
+    properties: &tiled::Properties,
+    name: &str,
+    asset_server: &AssetServer,
+) -> Result<T, TiledPropertyError> {
+    let Some(value) = properties.get(name) else {
+        return Err(TiledPropertyError::Missing {
+            name: name.to_string(),
+        });
+    };
+    T::from_tiled_property(value, asset_server).map_err(|e| e.with_parent(name))
+}
+
+/// Reads the property by name, returns `Default` value if it is missing.
+pub fn get_property_or_default<T: FromTiledProperty + Default>(
+    properties: &tiled::Properties,
+    name: &str,
+    asset_server: &AssetServer,
+) -> Result<T, TiledPropertyError> {
+    get_property_or(properties, name, asset_server, T::default())
+}
+
+/// Reads the property by name, returns `default` if it is missing.
+pub fn get_property_or<T: FromTiledProperty>(
+    properties: &tiled::Properties,
+    name: &str,
+    asset_server: &AssetServer,
+    default: T,
+) -> Result<T, TiledPropertyError> {
+    match get_property(properties, name, asset_server) {
+        Err(TiledPropertyError::Missing { .. }) => Ok(default),
+        result => result,
+    }
+}
+
+/// Builds the type from a class property.
+pub fn from_class_property<T: FromTiledProperties>(
+    value: &tiled::PropertyValue,
+    asset_server: &AssetServer,
+) -> Result<T, TiledPropertyError> {
+    match value {
+        tiled::PropertyValue::ClassValue { properties, .. } => {
+            T::from_tiled_properties(properties, asset_server)
+        }
+        _ => Err(TiledPropertyError::mismatch("class", value)),
+    }
+}
+
+/// Builds the component from properties and inserts it, used by
+/// `#[derive(TiledComponent)]`.
+pub fn insert_from_properties<T: FromTiledProperties + Component>(
+    commands: &mut EntityCommands,
+    values: HashMap<String, tiled::PropertyValue>,
+    asset_server: &AssetServer,
+) -> Result<(), TiledPropertyError> {
+    let properties: tiled::Properties = values.into_iter().collect();
+    let component = T::from_tiled_properties(&properties, asset_server)?;
+    commands.insert(component);
+    Ok(())
+}
+
+/// Returns the name of the property type as it is called in Tiled.
+pub(crate) fn property_type_name(value: &tiled::PropertyValue) -> &'static str {
+    match value {
+        tiled::PropertyValue::BoolValue(_) => "bool",
+        tiled::PropertyValue::FloatValue(_) => "float",
+        tiled::PropertyValue::IntValue(_) => "int",
+        tiled::PropertyValue::ColorValue(_) => "color",
+        tiled::PropertyValue::StringValue(_) => "string",
+        tiled::PropertyValue::FileValue(_) => "file",
+        tiled::PropertyValue::ObjectValue(_) => "object",
+        tiled::PropertyValue::ClassValue { .. } => "class",
+    }
+}
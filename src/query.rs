@@ -0,0 +1,404 @@
+//! This module contains `TiledMapQuery` system parameter, which finds tiles of
+//! spawned maps at world positions.
+
+use bevy::ecs::query::Has;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+use crate::components::{
+    CenteredTileGrid, LayerStorage, TileLayerId, TilePos, TileStorage,
+    TiledProperties,
+};
+use crate::project::TiledProjectAsset;
+use crate::resources::TiledProjectResource;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Finds tiles of spawned maps at world positions, respecting transforms of
+/// the map and its layers.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn system_check_ground(
+///     map_query: TiledMapQuery,
+///     tilemap_query: Query<Entity, With<Handle<TiledMapAsset>>>,
+///     player_query: Query<&GlobalTransform, With<Player>>,
+/// ) {
+///     let Ok(player) = player_query.get_single() else {
+///         return;
+///     };
+///     let position = player.translation().truncate();
+///     for map in tilemap_query.iter() {
+///         if let Some(tile) = map_query.tile_at(map, "Ground", position) {
+///             let ground = tile.properties.get_string("ground");
+///             info!("Walking on {:?}", ground);
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct TiledMapQuery<'w, 's> {
+    maps: Query<
+        'w,
+        's,
+        (
+            &'static Handle<TiledMapAsset>,
+            &'static TileStorage,
+            &'static LayerStorage,
+        ),
+    >,
+    layers: Query<'w, 's, (&'static GlobalTransform, Has<CenteredTileGrid>)>,
+    map_assets: Res<'w, Assets<TiledMapAsset>>,
+    projects: Res<'w, Assets<TiledProjectAsset>>,
+    project: Option<Res<'w, TiledProjectResource>>,
+}
+
+/// Tile found by [TiledMapQuery].
+#[derive(Debug, Clone)]
+pub struct TiledTileInfo<'a> {
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub map: Entity,
+    /// Index of the tile layer.
+    pub layer: usize,
+    pub tile_pos: TilePos,
+    /// Tile entity.
+    pub entity: Entity,
+    /// Id of the tile in its tileset.
+    pub tile_id: tiled::TileId,
+    pub tileset: &'a tiled::Tileset,
+    /// Class and properties of the tile from its tileset.
+    pub properties: TiledProperties,
+}
+
+/// Asset and storages of a single map, fetched once per query.
+#[derive(Clone, Copy)]
+struct MapData<'a> {
+    asset: &'a TiledMapAsset,
+    tile_storage: &'a TileStorage,
+    layer_storage: &'a LayerStorage,
+}
+
+impl<'w, 's> TiledMapQuery<'w, 's> {
+    /// Returns the position of the tile under the world point in the tile
+    /// layer, even if the cell is empty.
+    pub fn tile_pos_at<'a>(
+        &self,
+        map: Entity,
+        layer: impl Into<TileLayerId<'a>>,
+        world_pos: Vec2,
+    ) -> Option<TilePos> {
+        let map_data = self.map_data(map)?;
+        let layer_idx = map_data.tile_storage.layer_index(layer).ok()?;
+        self.layer_tile_pos_at(map_data, layer_idx, world_pos)
+    }
+
+    /// Returns the tile under the world point in the tile layer.
+    pub fn tile_at<'a>(
+        &self,
+        map: Entity,
+        layer: impl Into<TileLayerId<'a>>,
+        world_pos: Vec2,
+    ) -> Option<TiledTileInfo<'_>> {
+        let map_data = self.map_data(map)?;
+        let layer_idx = map_data.tile_storage.layer_index(layer).ok()?;
+        self.layer_tile_at(map, map_data, layer_idx, world_pos)
+    }
+
+    /// Returns tiles under the world point in all tile layers, from the top
+    /// layer to the bottom one.
+    pub fn tiles_at(
+        &self,
+        map: Entity,
+        world_pos: Vec2,
+    ) -> Vec<TiledTileInfo<'_>> {
+        let Some(map_data) = self.map_data(map) else {
+            return Vec::new();
+        };
+        let mut layers: Vec<usize> = map_data.tile_storage.layers().collect();
+        layers.sort_unstable_by(|a, b| b.cmp(a));
+        layers
+            .into_iter()
+            .filter_map(|layer_idx| {
+                self.layer_tile_at(map, map_data, layer_idx, world_pos)
+            })
+            .collect()
+    }
+
+    /// Returns the tile under the world point in the topmost tile layer,
+    /// which has a tile there.
+    pub fn top_tile_at(
+        &self,
+        map: Entity,
+        world_pos: Vec2,
+    ) -> Option<TiledTileInfo<'_>> {
+        self.tiles_at(map, world_pos).into_iter().next()
+    }
+
+    fn map_data(&self, map: Entity) -> Option<MapData<'_>> {
+        let (handle, tile_storage, layer_storage) = self.maps.get(map).ok()?;
+        Some(MapData {
+            asset: self.map_assets.get(handle)?,
+            tile_storage,
+            layer_storage,
+        })
+    }
+
+    fn layer_tile_pos_at(
+        &self,
+        map_data: MapData,
+        layer_idx: usize,
+        world_pos: Vec2,
+    ) -> Option<TilePos> {
+        let layer_entity = map_data.layer_storage.get(layer_idx)?;
+        let (transform, centered) = self.layers.get(layer_entity).ok()?;
+
+        let tile_size = Vec2::new(
+            map_data.asset.map.tile_width as f32,
+            map_data.asset.map.tile_height as f32,
+        );
+        let mut local_pos = transform
+            .affine()
+            .inverse()
+            .transform_point3(world_pos.extend(0.))
+            .truncate();
+        if centered {
+            local_pos += tile_size * 0.5;
+        }
+        TilePos::from_world(
+            local_pos,
+            map_data.tile_storage.layer_size(layer_idx).ok()?,
+            tile_size,
+        )
+    }
+
+    fn layer_tile_at<'a>(
+        &'a self,
+        map: Entity,
+        map_data: MapData<'a>,
+        layer_idx: usize,
+        world_pos: Vec2,
+    ) -> Option<TiledTileInfo<'a>> {
+        let tile_storage = map_data.tile_storage;
+        let tile_pos =
+            self.layer_tile_pos_at(map_data, layer_idx, world_pos)?;
+        let entity = tile_storage.get(layer_idx, &tile_pos).ok()?;
+
+        let map_asset = map_data.asset;
+        let tile_layer = map_asset.map.get_layer(layer_idx)?.as_tile_layer()?;
+        let tiled_pos =
+            tile_pos.to_tiled(tile_storage.layer_size(layer_idx).ok()?);
+        let layer_tile =
+            tile_layer.get_tile(tiled_pos.x as i32, tiled_pos.y as i32)?;
+        let properties = match layer_tile.get_tile() {
+            Some(tile) => TiledProperties::new(
+                tile.user_type.as_deref(),
+                &tile.properties,
+            )
+            .with_defaults(self.project())
+            .with_files(&map_asset.files),
+            None => TiledProperties::default(),
+        };
+        Some(TiledTileInfo {
+            map,
+            layer: layer_idx,
+            tile_pos,
+            entity,
+            tile_id: layer_tile.id(),
+            tileset: layer_tile.get_tileset(),
+            properties,
+        })
+    }
+
+    fn project(&self) -> Option<&TiledProjectAsset> {
+        self.projects.get(&self.project.as_ref()?.handle)
+    }
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use bevy::ecs::system::SystemState;
+
+    use super::*;
+    use crate::components::TiledLayer;
+    use crate::settings::TiledMapAnchor;
+
+    /// 4x3 map of 16x16 tiles, `Ground` layer is filled with tile 0, which
+    /// has `ground` property, `Top` layer has tile 1 at `TilePos(1, 2)`.
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down"
+     width="4" height="3" tilewidth="16" tileheight="16" infinite="0">
+ <tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16"
+          tilecount="2" columns="2">
+  <image source="tiles.png" width="32" height="16"/>
+  <tile id="0">
+   <properties>
+    <property name="ground" value="grass"/>
+   </properties>
+  </tile>
+ </tileset>
+ <layer id="1" name="Ground" width="4" height="3">
+  <data encoding="csv">1,1,1,1,1,1,1,1,1,1,1,1</data>
+ </layer>
+ <layer id="2" name="Top" width="4" height="3">
+  <data encoding="csv">0,2,0,0,0,0,0,0,0,0,0,0</data>
+ </layer>
+</map>"#;
+
+    pub(crate) fn initialize_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_asset::<TiledMapAsset>()
+            .add_asset::<TiledProjectAsset>();
+        app
+    }
+
+    /// Spawns the test map with the global transform of its layers, tiles
+    /// are spawned as empty entities.
+    pub(crate) fn spawn_map(
+        app: &mut App,
+        layer_transform: GlobalTransform,
+        centered: bool,
+    ) -> Entity {
+        let map_asset = TiledMapAsset::from_tmx(MAP);
+        let map_size = UVec2::new(map_asset.map.width, map_asset.map.height);
+        let map = app.world.spawn_empty().id();
+        let mut tile_storage = TileStorage::new();
+        let mut layer_storage = LayerStorage::new();
+        for (layer_idx, layer) in map_asset.map.layers().enumerate() {
+            let mut layer_entity = app.world.spawn((
+                TiledLayer {
+                    map,
+                    index: layer_idx,
+                },
+                layer_transform,
+            ));
+            if centered {
+                layer_entity.insert(CenteredTileGrid);
+            }
+            layer_storage.set(
+                layer_idx,
+                layer_entity.id(),
+                layer.id,
+                &layer.name,
+                layer.user_type.as_deref(),
+            );
+            let tile_layer = layer.as_tile_layer().unwrap();
+            tile_storage.init_place(layer_idx, map_size).unwrap();
+            tile_storage.set_layer_name(layer_idx, &layer.name);
+            for x in 0..map_size.x {
+                for y in 0..map_size.y {
+                    if tile_layer.get_tile(x as i32, y as i32).is_none() {
+                        continue;
+                    }
+                    let tile_pos =
+                        TilePos::from_tiled(UVec2::new(x, y), map_size);
+                    let tile = app.world.spawn(tile_pos).id();
+                    tile_storage.set(layer_idx, &tile_pos, tile).unwrap();
+                }
+            }
+        }
+        let handle = app
+            .world
+            .resource_mut::<Assets<TiledMapAsset>>()
+            .add(map_asset);
+        app.world
+            .entity_mut(map)
+            .insert((handle, tile_storage, layer_storage));
+        map
+    }
+
+    /// Map is centered at (100, 50) and scaled twice, so its bottom-left
+    /// corner is at (36, 2) and tiles are 32 units wide.
+    fn spawn_transformed_map(app: &mut App) -> Entity {
+        let map_transform = Transform::from_xyz(100., 50., 0.)
+            .with_scale(Vec3::new(2., 2., 1.));
+        let anchor = TiledMapAnchor::Center.offset(Vec2::new(64., 48.));
+        let layer_transform = GlobalTransform::from(map_transform)
+            * Transform::from_translation(anchor.extend(0.));
+        spawn_map(app, layer_transform, false)
+    }
+
+    #[test]
+    fn test_tile_pos_at() {
+        let mut app = initialize_app();
+        let map = spawn_transformed_map(&mut app);
+        let mut state: SystemState<TiledMapQuery> =
+            SystemState::new(&mut app.world);
+        let map_query = state.get(&app.world);
+
+        let tile_pos_at =
+            |x, y| map_query.tile_pos_at(map, "Ground", Vec2::new(x, y));
+        assert_eq!(tile_pos_at(37., 3.), Some(TilePos::new(0, 0)));
+        assert_eq!(tile_pos_at(84., 82.), Some(TilePos::new(1, 2)));
+        assert_eq!(tile_pos_at(163., 97.), Some(TilePos::new(3, 2)));
+        // Outside of the map
+        assert_eq!(tile_pos_at(35., 3.), None);
+        assert_eq!(tile_pos_at(37., 1.), None);
+        assert_eq!(tile_pos_at(165., 50.), None);
+        assert_eq!(tile_pos_at(84., 99.), None);
+        // Unknown layer or map
+        assert_eq!(map_query.tile_pos_at(map, "Sky", Vec2::new(37., 3.)), None);
+        let not_map = Entity::from_raw(1000);
+        assert_eq!(
+            map_query.tile_pos_at(not_map, 0usize, Vec2::new(37., 3.)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tile_pos_at_centered_grid() {
+        let mut app = initialize_app();
+        let map = spawn_map(&mut app, GlobalTransform::IDENTITY, true);
+        let mut state: SystemState<TiledMapQuery> =
+            SystemState::new(&mut app.world);
+        let map_query = state.get(&app.world);
+
+        // Tile centers are at multiples of the tile size
+        let tile_pos_at =
+            |x, y| map_query.tile_pos_at(map, 0usize, Vec2::new(x, y));
+        assert_eq!(tile_pos_at(0., 0.), Some(TilePos::new(0, 0)));
+        assert_eq!(tile_pos_at(-7., 7.), Some(TilePos::new(0, 0)));
+        assert_eq!(tile_pos_at(9., 0.), Some(TilePos::new(1, 0)));
+        assert_eq!(tile_pos_at(55., 39.), Some(TilePos::new(3, 2)));
+        assert_eq!(tile_pos_at(-9., 0.), None);
+        assert_eq!(tile_pos_at(0., 41.), None);
+    }
+
+    #[test]
+    fn test_tiles_at() {
+        let mut app = initialize_app();
+        let map = spawn_transformed_map(&mut app);
+        let mut state: SystemState<TiledMapQuery> =
+            SystemState::new(&mut app.world);
+        let map_query = state.get(&app.world);
+
+        // Only `Ground` has a tile here
+        let ground = map_query.tile_at(map, "Ground", Vec2::new(37., 3.));
+        let ground = ground.unwrap();
+        assert_eq!(ground.layer, 0);
+        assert_eq!(ground.tile_id, 0);
+        assert_eq!(ground.properties.get_string("ground"), Ok("grass"));
+        assert!(map_query.tile_at(map, "Top", Vec2::new(37., 3.)).is_none());
+
+        // Both layers have tiles here, the top one goes first
+        let tiles = map_query.tiles_at(map, Vec2::new(84., 82.));
+        let layers: Vec<usize> = tiles.iter().map(|tile| tile.layer).collect();
+        assert_eq!(layers, [1, 0]);
+        assert_eq!(tiles[0].tile_id, 1);
+        assert_eq!(tiles[0].tile_pos, TilePos::new(1, 2));
+        let top = map_query.top_tile_at(map, Vec2::new(84., 82.)).unwrap();
+        assert_eq!(top.entity, tiles[0].entity);
+
+        assert!(map_query.tiles_at(map, Vec2::new(0., 0.)).is_empty());
+        assert!(map_query.top_tile_at(map, Vec2::new(0., 0.)).is_none());
+    }
+}
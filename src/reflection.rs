@@ -0,0 +1,168 @@
+//! Inserting any reflected component, which type name matches a Tiled class.
+
+use bevy::ecs::system::Command;
+use bevy::prelude::*;
+use bevy::reflect::{DynamicStruct, StructInfo, TypeInfo, TypeRegistry};
+use bevy::utils::HashMap;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::error::{TiledErrorContext, TiledMapError};
+use crate::property_value::property_type_name;
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Inserts components registered in `AppTypeRegistry` with `ReflectComponent`,
+/// which short or full type name is one of `classes`. Fields are populated
+/// from properties by name, other fields keep their `Default` values.
+pub(crate) struct InsertReflectedComponents {
+    pub(crate) entity: Entity,
+    pub(crate) classes: Vec<String>,
+    pub(crate) properties: HashMap<String, tiled::PropertyValue>,
+    pub(crate) context: TiledErrorContext,
+}
+
+impl Command for InsertReflectedComponents {
+    fn apply(self, world: &mut World) {
+        let Some(registry) = world.get_resource::<AppTypeRegistry>() else {
+            return;
+        };
+        let registry = registry.clone();
+        let registry = registry.read();
+
+        let mut errors = Vec::new();
+        for class in self.classes.iter() {
+            let Some(registration) = registry
+                .get_with_short_name(class)
+                .or_else(|| registry.get_with_name(class))
+            else {
+                continue;
+            };
+            let Some(reflect_component) =
+                registration.data::<ReflectComponent>()
+            else {
+                continue;
+            };
+            let TypeInfo::Struct(info) = registration.type_info() else {
+                warn!("Reflected Tiled class {} is not a struct", class);
+                continue;
+            };
+
+            let component = match properties_to_struct(
+                &registry,
+                info,
+                self.properties.iter(),
+            ) {
+                Ok(component) => component,
+                Err(e) => {
+                    errors.push(TiledMapError::ReflectTypeMismatch {
+                        context: self.context.clone(),
+                        component: class.clone(),
+                        field: e.field,
+                        expected: e.expected.to_string(),
+                        found: e.found,
+                    });
+                    continue;
+                }
+            };
+            let Some(mut entity) = world.get_entity_mut(self.entity) else {
+                return;
+            };
+            reflect_component.insert(&mut entity, &component);
+        }
+
+        for error in errors {
+            error!("{}", error);
+            world.send_event(error);
+        }
+    }
+}
+
+/// Property which can't be assigned to the field.
+struct ReflectPropertyError {
+    /// Dotted path to the field.
+    field: String,
+    /// Type name of the field.
+    expected: &'static str,
+    /// Type of the property.
+    found: &'static str,
+}
+
+impl ReflectPropertyError {
+    fn nested(self, parent: &str) -> Self {
+        ReflectPropertyError {
+            field: format!("{}.{}", parent, self.field),
+            ..self
+        }
+    }
+}
+
+/// Builds a dynamic struct with fields of `info` found in `properties`,
+/// unknown properties are ignored.
+fn properties_to_struct<'p>(
+    registry: &TypeRegistry,
+    info: &StructInfo,
+    properties: impl Iterator<Item = (&'p String, &'p tiled::PropertyValue)>,
+) -> Result<DynamicStruct, ReflectPropertyError> {
+    let mut dynamic = DynamicStruct::default();
+    for (name, value) in properties {
+        let Some(field) = info.field(name) else {
+            continue;
+        };
+        let mismatch = || ReflectPropertyError {
+            field: name.clone(),
+            expected: field.type_name(),
+            found: property_type_name(value),
+        };
+        let reflected: Box<dyn Reflect> = match value {
+            tiled::PropertyValue::BoolValue(v) if field.is::<bool>() => {
+                Box::new(*v)
+            }
+            tiled::PropertyValue::FloatValue(v) if field.is::<f32>() => {
+                Box::new(*v)
+            }
+            tiled::PropertyValue::FloatValue(v) if field.is::<f64>() => {
+                Box::new(*v as f64)
+            }
+            tiled::PropertyValue::IntValue(v) if field.is::<i32>() => {
+                Box::new(*v)
+            }
+            tiled::PropertyValue::IntValue(v) if field.is::<f32>() => {
+                Box::new(*v as f32)
+            }
+            tiled::PropertyValue::IntValue(v) if field.is::<u32>() => {
+                match u32::try_from(*v) {
+                    Ok(v) => Box::new(v),
+                    Err(_) => return Err(mismatch()),
+                }
+            }
+            tiled::PropertyValue::ObjectValue(v) if field.is::<u32>() => {
+                Box::new(*v)
+            }
+            tiled::PropertyValue::StringValue(v)
+            | tiled::PropertyValue::FileValue(v)
+                if field.is::<String>() =>
+            {
+                Box::new(v.clone())
+            }
+            #[cfg(feature = "render")]
+            tiled::PropertyValue::ColorValue(v) if field.is::<Color>() => {
+                Box::new(crate::plugin::tiled_color_to_bevy(v))
+            }
+            tiled::PropertyValue::ClassValue { properties, .. } => {
+                let Some(TypeInfo::Struct(nested)) =
+                    registry.get_type_info(field.type_id())
+                else {
+                    return Err(mismatch());
+                };
+                let nested =
+                    properties_to_struct(registry, nested, properties.iter())
+                        .map_err(|e| e.nested(name))?;
+                Box::new(nested)
+            }
+            _ => return Err(mismatch()),
+        };
+        dynamic.insert_boxed(name, reflected);
+    }
+    Ok(dynamic)
+}
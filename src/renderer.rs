@@ -0,0 +1,203 @@
+//! This module contains `TiledLayerRenderer` trait, which spawns layers of the
+//! map, and its built-in implementations.
+
+use bevy::log;
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::asset_loader::TiledMapAsset;
+use crate::components::{ObjectStorage, TileStorage};
+use crate::error::{TiledErrorContext, TiledMapError};
+use crate::events::TiledObjectSpawned;
+use crate::project::TiledProjectAsset;
+use crate::resources::{
+    TiledComponentResource, TiledLayerHandlerResource,
+    TiledLayerRendererResource,
+};
+use crate::settings::{
+    TiledLayerSettings, TiledMapSpawnSettings, TiledToolkitSettings,
+};
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Backend for spawning a single layer of the map. Register your own with
+/// [TiledLayerRendererReg](crate::prelude::TiledLayerRendererReg), and select
+/// it on the layer in Tiled with the `renderer` string property, or with the
+/// layer class equal to the renderer name.
+///
+/// Built-in renderers are [SpritesRenderer] (`sprites`) and
+/// `BevyEcsTilemapRenderer` (`bevy_ecs_tilemap`).
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+/// use bevy_tiled_toolkit::renderer::{MapSpawnContext, TiledLayerRenderer};
+/// use bevy_tiled_toolkit::error::TiledErrorContext;
+/// use bevy_tiled_toolkit::settings::TiledLayerSettings;
+///
+/// struct MeshRenderer;
+///
+/// impl TiledLayerRenderer for MeshRenderer {
+///     fn spawn_layer(
+///         &self,
+///         ctx: &mut MapSpawnContext,
+///         layer: &tiled::Layer,
+///         layer_idx: usize,
+///         layer_settings: &TiledLayerSettings,
+///         context: &TiledErrorContext,
+///     ) -> Entity {
+///         let transform = layer_settings
+///             .layer_transform(Vec2::new(layer.offset_x, -layer.offset_y));
+///         // Build a single mesh for the whole layer here.
+///         ctx.commands()
+///             .spawn(SpatialBundle::from_transform(transform))
+///             .id()
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.register_layer_renderer("mesh", MeshRenderer);
+/// ```
+pub trait TiledLayerRenderer: Send + Sync + 'static {
+    /// Spawn the layer entity with all its content and return it. The layer
+    /// entity is named and attached to the map entity afterwards. Errors
+    /// should be reported with [MapSpawnContext::report].
+    fn spawn_layer(
+        &self,
+        ctx: &mut MapSpawnContext,
+        layer: &tiled::Layer,
+        layer_idx: usize,
+        layer_settings: &TiledLayerSettings,
+        context: &TiledErrorContext,
+    ) -> Entity;
+}
+
+/// Spawns each tile and object as a separate `SpriteSheetBundle`. Without
+/// sprites they are spawned only with transforms.
+pub struct SpritesRenderer;
+
+impl TiledLayerRenderer for SpritesRenderer {
+    fn spawn_layer(
+        &self,
+        ctx: &mut MapSpawnContext,
+        layer: &tiled::Layer,
+        layer_idx: usize,
+        layer_settings: &TiledLayerSettings,
+        context: &TiledErrorContext,
+    ) -> Entity {
+        crate::plugin::spawn_with_sprites(
+            ctx,
+            layer,
+            layer_idx,
+            layer_settings,
+            context,
+        )
+    }
+}
+
+/// Renders the whole tile layer with `bevy_ecs_tilemap`.
+#[cfg(feature = "bevy_ecs_tilemap")]
+pub struct BevyEcsTilemapRenderer;
+
+#[cfg(feature = "bevy_ecs_tilemap")]
+impl TiledLayerRenderer for BevyEcsTilemapRenderer {
+    fn spawn_layer(
+        &self,
+        ctx: &mut MapSpawnContext,
+        layer: &tiled::Layer,
+        layer_idx: usize,
+        layer_settings: &TiledLayerSettings,
+        context: &TiledErrorContext,
+    ) -> Entity {
+        crate::plugin::spawn_with_bevy_ecs_tilemap(
+            ctx,
+            layer,
+            layer_idx,
+            layer_settings,
+            context,
+        )
+    }
+}
+
+/// State shared by all spawning functions while a single map is spawned.
+pub struct MapSpawnContext<'a, 'w, 's> {
+    pub(crate) commands: &'a mut Commands<'w, 's>,
+    pub(crate) asset_server: &'a Res<'a, AssetServer>,
+    pub(crate) tiled_components: &'a TiledComponentResource,
+    pub(crate) layer_handlers: &'a TiledLayerHandlerResource,
+    pub(crate) renderers: &'a TiledLayerRendererResource,
+    pub(crate) settings: &'a TiledToolkitSettings,
+    pub(crate) spawn_settings: &'a TiledMapSpawnSettings,
+    pub(crate) tilemap_asset: &'a TiledMapAsset,
+    pub(crate) project: Option<&'a TiledProjectAsset>,
+    pub(crate) map_entity: Entity,
+    pub(crate) map_handle: &'a Handle<TiledMapAsset>,
+    pub(crate) tile_storage: &'a mut TileStorage,
+    pub(crate) object_storage: &'a mut ObjectStorage,
+    /// Errors are collected while spawning and sent afterwards.
+    pub(crate) errors: Vec<TiledMapError>,
+    pub(crate) spawned_objects: Vec<TiledObjectSpawned>,
+    /// Object properties, which are resolved when all objects are spawned.
+    pub(crate) object_refs: Vec<PendingObjectRef>,
+}
+
+/// Object property of the spawned object, which references another object.
+pub(crate) struct PendingObjectRef {
+    pub(crate) entity: Entity,
+    pub(crate) property: String,
+    pub(crate) target: u32,
+    pub(crate) context: TiledErrorContext,
+}
+
+impl<'a, 'w, 's> MapSpawnContext<'a, 'w, 's> {
+    /// Log the error and queue it for sending.
+    pub fn report(&mut self, error: TiledMapError) {
+        log::error!("{}", error);
+        self.errors.push(error);
+    }
+
+    pub fn commands(&mut self) -> &mut Commands<'w, 's> {
+        self.commands
+    }
+
+    pub fn asset_server(&self) -> &AssetServer {
+        self.asset_server
+    }
+
+    pub fn settings(&self) -> &TiledToolkitSettings {
+        self.settings
+    }
+
+    pub fn spawn_settings(&self) -> &TiledMapSpawnSettings {
+        self.spawn_settings
+    }
+
+    /// The map being spawned.
+    pub fn tilemap_asset(&self) -> &'a TiledMapAsset {
+        self.tilemap_asset
+    }
+
+    /// Project from `TiledToolkitSettings::project`, if it is loaded.
+    pub fn project(&self) -> Option<&'a TiledProjectAsset> {
+        self.project
+    }
+
+    /// Entity with `Handle<TiledMapAsset>`.
+    pub fn map_entity(&self) -> Entity {
+        self.map_entity
+    }
+
+    pub fn map_handle(&self) -> &Handle<TiledMapAsset> {
+        self.map_handle
+    }
+
+    /// Store spawned tiles here to make them reachable from user code.
+    pub fn tile_storage(&mut self) -> &mut TileStorage {
+        self.tile_storage
+    }
+
+    /// Store spawned objects here to resolve references to them.
+    pub fn object_storage(&mut self) -> &mut ObjectStorage {
+        self.object_storage
+    }
+}
@@ -3,18 +3,102 @@ use bevy::utils::HashMap;
 
 // ───── Current Crate Imports ────────────────────────────────────────────── //
 
-use crate::TiledComponent;
+use crate::factory::TiledComponentFactory;
+use crate::project::TiledProjectAsset;
+use crate::renderer::{SpritesRenderer, TiledLayerRenderer};
+use crate::settings::TiledLayerSettings;
+use crate::{TiledComponent, TiledLayerHandler};
 
 // ───── Body ─────────────────────────────────────────────────────────────── //
 
 #[derive(Resource, Default)]
 pub(crate) struct TiledComponentResource {
     pub(crate) vec: Vec<Box<dyn TiledComponent + Send + Sync>>,
+    /// Factories with classes they are registered for.
+    pub(crate) factories: Vec<(String, Box<dyn TiledComponentFactory>)>,
 }
 
 impl TiledComponentResource {
     pub(crate) fn new() -> Self {
-        TiledComponentResource { vec: vec![] }
+        TiledComponentResource {
+            vec: vec![],
+            factories: vec![],
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub(crate) struct TiledLayerHandlerResource {
+    pub(crate) vec: Vec<Box<dyn TiledLayerHandler + Send + Sync>>,
+}
+
+impl TiledLayerHandlerResource {
+    pub(crate) fn new() -> Self {
+        TiledLayerHandlerResource { vec: vec![] }
+    }
+
+    /// Returns handlers registered for the layer class.
+    pub(crate) fn for_layer<'a>(
+        &'a self,
+        layer: &'a tiled::Layer,
+    ) -> impl Iterator<Item = &'a (dyn TiledLayerHandler + Send + Sync)> {
+        self.vec
+            .iter()
+            .filter(move |handler| {
+                layer.user_type.as_deref() == Some(handler.get_class_name())
+            })
+            .map(|handler| handler.as_ref())
+    }
+}
+
+/// Project from `TiledToolkitSettings::project`. It's `pub` only because
+/// [TiledMapQuery](crate::query::TiledMapQuery) reads it, the module is
+/// private.
+#[derive(Resource)]
+pub struct TiledProjectResource {
+    pub(crate) handle: Handle<TiledProjectAsset>,
+}
+
+/// Layer renderers by name, built-in renderers are registered by default.
+#[derive(Resource)]
+pub(crate) struct TiledLayerRendererResource {
+    pub(crate) renderers: HashMap<String, Box<dyn TiledLayerRenderer>>,
+}
+
+impl Default for TiledLayerRendererResource {
+    fn default() -> Self {
+        let mut renderers: HashMap<String, Box<dyn TiledLayerRenderer>> =
+            HashMap::new();
+        renderers.insert("sprites".to_string(), Box::new(SpritesRenderer));
+        #[cfg(feature = "bevy_ecs_tilemap")]
+        renderers.insert(
+            "bevy_ecs_tilemap".to_string(),
+            Box::new(crate::renderer::BevyEcsTilemapRenderer),
+        );
+        TiledLayerRendererResource { renderers }
+    }
+}
+
+impl TiledLayerRendererResource {
+    /// Returns the renderer for the layer: explicitly set with the `renderer`
+    /// property, registered for the layer class, or the default one.
+    pub(crate) fn get_for_layer(
+        &self,
+        layer: &tiled::Layer,
+        layer_settings: &TiledLayerSettings,
+    ) -> Option<&dyn TiledLayerRenderer> {
+        if !layer_settings.explicit_renderer {
+            if let Some(renderer) = layer
+                .user_type
+                .as_ref()
+                .and_then(|class| self.renderers.get(class))
+            {
+                return Some(renderer.as_ref());
+            }
+        }
+        self.renderers
+            .get(layer_settings.renderer.name())
+            .map(|renderer| renderer.as_ref())
     }
 }
 
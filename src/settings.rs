@@ -0,0 +1,448 @@
+//! This module contains `TiledToolkitSettings` type, which configures the
+//! [TiledToolkitPlugin](crate::prelude::TiledToolkitPlugin).
+
+use bevy::ecs::schedule::BoxedScheduleLabel;
+use bevy::prelude::*;
+
+// ───── Current Crate Imports ────────────────────────────────────────────── //
+
+use crate::error::{TiledErrorContext, TiledMapError};
+
+// ───── Body ─────────────────────────────────────────────────────────────── //
+
+/// Backend for spawning layers, the name of a
+/// [TiledLayerRenderer](crate::renderer::TiledLayerRenderer) registered in
+/// the [App].
+#[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum TiledRenderBackend {
+    /// Each tile is spawned as a `SpriteSheetBundle`.
+    #[default]
+    Sprites,
+    /// The whole layer is rendered with `bevy_ecs_tilemap`.
+    BevyEcsTilemap,
+    /// Renderer registered with
+    /// [TiledLayerRendererReg](crate::prelude::TiledLayerRendererReg).
+    Custom(String),
+}
+
+impl TiledRenderBackend {
+    /// Parses the value of the `renderer` layer property.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "sprites" => TiledRenderBackend::Sprites,
+            "bevy_ecs_tilemap" => TiledRenderBackend::BevyEcsTilemap,
+            _ => TiledRenderBackend::Custom(name.to_string()),
+        }
+    }
+
+    /// Name of the renderer, the same as the `renderer` property value.
+    pub fn name(&self) -> &str {
+        match self {
+            TiledRenderBackend::Sprites => "sprites",
+            TiledRenderBackend::BevyEcsTilemap => "bevy_ecs_tilemap",
+            TiledRenderBackend::Custom(name) => name,
+        }
+    }
+}
+
+/// Global settings of the plugin. Every setting, except `schedule` and
+/// `project`, can be overridden for a particular layer with a property in
+/// Tiled:
+///
+/// | Setting            | Layer property                             |
+/// |--------------------|--------------------------------------------|
+/// | `default_renderer` | `renderer`: name of a registered renderer   |
+/// | `layer_z_spacing`  | `z`: float, absolute z of the layer         |
+/// | `pixels_per_unit`  | `pixels_per_unit`: float                    |
+/// | `spawn_colliders`  | `spawn_colliders`: bool                     |
+/// | `y_sort`           | `y_sort`: bool                              |
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// let mut app = App::new();
+/// app.add_plugins(TiledToolkitPlugin {
+///     settings: TiledToolkitSettings {
+///         pixels_per_unit: 16.,
+///         y_sort: true,
+///         ..default()
+///     },
+/// });
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct TiledToolkitSettings {
+    /// Backend for tile layers without `renderer` property.
+    pub default_renderer: TiledRenderBackend,
+    /// Distance by z between neighbouring layers, layer z is
+    /// `layer_index * layer_z_spacing`.
+    pub layer_z_spacing: f32,
+    /// How many Tiled pixels fit into one Bevy unit.
+    pub pixels_per_unit: f32,
+    /// Asset path of the `.tiled-project` file. Defaults of its custom
+    /// classes are merged into properties, and maps are spawned only when
+    /// the project is loaded.
+    pub project: Option<String>,
+    /// Schedule to run all systems of the plugin in.
+    pub schedule: BoxedScheduleLabel,
+    /// Spawn colliders from tile collision shapes, works only with `rapier2d`
+    /// feature.
+    pub spawn_colliders: bool,
+    /// Sort tiles and objects by z according to their y position, so lower
+    /// sprites are drawn in front of higher ones. Has no effect on layers
+    /// rendered with `bevy_ecs_tilemap`.
+    pub y_sort: bool,
+}
+
+impl Default for TiledToolkitSettings {
+    fn default() -> Self {
+        TiledToolkitSettings {
+            default_renderer: TiledRenderBackend::default(),
+            layer_z_spacing: 1.,
+            pixels_per_unit: 1.,
+            project: None,
+            schedule: Box::new(Update),
+            spawn_colliders: true,
+            y_sort: false,
+        }
+    }
+}
+
+impl TiledToolkitSettings {
+    /// Resolve settings for the particular layer, applying overrides from the
+    /// layer properties. Invalid values are replaced with defaults, and
+    /// returned as errors.
+    pub fn layer_settings(
+        &self,
+        layer: &tiled::Layer,
+        layer_idx: usize,
+        context: &TiledErrorContext,
+    ) -> (TiledLayerSettings, Vec<TiledMapError>) {
+        let properties = &layer.properties;
+        let mut errors = Vec::new();
+        let mut renderer = self.default_renderer.clone();
+        let mut explicit_renderer = false;
+        // Legacy way to enable `bevy_ecs_tilemap` on the layer.
+        if let Some(true) = get_bool(properties, "bevy_ecs_tilemap") {
+            renderer = TiledRenderBackend::BevyEcsTilemap;
+            explicit_renderer = true;
+        }
+        match properties.get("renderer") {
+            Some(tiled::PropertyValue::StringValue(name))
+                if !name.is_empty() =>
+            {
+                renderer = TiledRenderBackend::from_name(name);
+                explicit_renderer = true;
+            }
+            Some(value) => errors.push(TiledMapError::UnknownRenderer {
+                context: context.clone(),
+                renderer: format!("{:?}", value),
+            }),
+            None => {}
+        }
+
+        let mut pixels_per_unit = self.pixels_per_unit;
+        if !is_valid_pixels_per_unit(pixels_per_unit) {
+            errors.push(TiledMapError::InvalidPixelsPerUnit {
+                context: context.clone(),
+                value: pixels_per_unit,
+            });
+            pixels_per_unit = TiledToolkitSettings::default().pixels_per_unit;
+        }
+        match get_f32(properties, "pixels_per_unit") {
+            Some(value) if is_valid_pixels_per_unit(value) => {
+                pixels_per_unit = value
+            }
+            Some(value) => errors.push(TiledMapError::InvalidPixelsPerUnit {
+                context: context.clone(),
+                value,
+            }),
+            None => {}
+        }
+
+        let layer_settings = TiledLayerSettings {
+            renderer,
+            explicit_renderer,
+            z: get_f32(properties, "z")
+                .unwrap_or(layer_idx as f32 * self.layer_z_spacing),
+            z_spacing: self.layer_z_spacing,
+            pixels_per_unit,
+            spawn_colliders: get_bool(properties, "spawn_colliders")
+                .unwrap_or(self.spawn_colliders),
+            y_sort: get_bool(properties, "y_sort").unwrap_or(self.y_sort),
+            spawn_sprites: cfg!(feature = "render"),
+            anchor_offset: Vec2::ZERO,
+        };
+        (layer_settings, errors)
+    }
+}
+
+/// Point of the map, which is placed at the map entity's translation.
+#[derive(Default, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum TiledMapAnchor {
+    #[default]
+    BottomLeft,
+    Center,
+    TopLeft,
+}
+
+impl TiledMapAnchor {
+    /// Returns offset in pixels, which should be applied to the map content
+    /// of the given size in pixels.
+    pub fn offset(&self, map_size: Vec2) -> Vec2 {
+        match self {
+            TiledMapAnchor::BottomLeft => Vec2::ZERO,
+            TiledMapAnchor::Center => -map_size * 0.5,
+            TiledMapAnchor::TopLeft => Vec2::new(0., -map_size.y),
+        }
+    }
+}
+
+/// Selects layers by name or class.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum TiledLayerFilter {
+    Name(String),
+    Class(String),
+}
+
+impl TiledLayerFilter {
+    /// Checks if the layer matches this filter.
+    pub fn matches(&self, layer: &tiled::Layer) -> bool {
+        match self {
+            TiledLayerFilter::Name(name) => layer.name == *name,
+            TiledLayerFilter::Class(class) => {
+                layer.user_type.as_deref() == Some(class.as_str())
+            }
+        }
+    }
+}
+
+/// Insert this component to the `Handle<TiledMapAsset>` entity (it is a part
+/// of [TiledMapBundle](crate::prelude::TiledMapBundle)) to alter spawning of
+/// this map instance only.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tiled_toolkit::prelude::*;
+///
+/// fn system_spawn_server_map(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+/// ) {
+///     commands.spawn(TiledMapBundle {
+///         tiled_map: asset_server.load("tiled/tilemaps/Map.tmx"),
+///         spawn_settings: TiledMapSpawnSettings {
+///             exclude_layers: vec![TiledLayerFilter::Class("Decor".into())],
+///             spawn_sprites: false,
+///             ..default()
+///         },
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct TiledMapSpawnSettings {
+    /// Spawn only layers matching any of these filters, all layers are
+    /// spawned if it is empty.
+    pub include_layers: Vec<TiledLayerFilter>,
+    /// Skip layers matching any of these filters.
+    pub exclude_layers: Vec<TiledLayerFilter>,
+    /// Spawn sprites and tilemaps. If `false`, tiles and objects are spawned
+    /// only with transforms, colliders and custom components.
+    pub spawn_sprites: bool,
+    /// Overrides `spawn_colliders` from global settings and layer properties.
+    pub spawn_colliders: Option<bool>,
+    pub anchor: TiledMapAnchor,
+}
+
+impl Default for TiledMapSpawnSettings {
+    fn default() -> Self {
+        TiledMapSpawnSettings {
+            include_layers: Vec::new(),
+            exclude_layers: Vec::new(),
+            spawn_sprites: true,
+            spawn_colliders: None,
+            anchor: TiledMapAnchor::default(),
+        }
+    }
+}
+
+impl TiledMapSpawnSettings {
+    /// Checks if the layer passes include and exclude filters.
+    pub fn spawns_layer(&self, layer: &tiled::Layer) -> bool {
+        (self.include_layers.is_empty()
+            || self.include_layers.iter().any(|f| f.matches(layer)))
+            && !self.exclude_layers.iter().any(|f| f.matches(layer))
+    }
+
+    /// Apply these settings on top of the resolved layer settings.
+    pub fn apply(
+        &self,
+        layer_settings: &mut TiledLayerSettings,
+        map: &tiled::Map,
+    ) {
+        let map_size = Vec2::new(
+            (map.width * map.tile_width) as f32,
+            (map.height * map.tile_height) as f32,
+        );
+        layer_settings.anchor_offset = self.anchor.offset(map_size);
+        layer_settings.spawn_sprites &= self.spawn_sprites;
+        if let Some(spawn_colliders) = self.spawn_colliders {
+            layer_settings.spawn_colliders = spawn_colliders;
+        }
+    }
+}
+
+/// Settings resolved for the particular layer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TiledLayerSettings {
+    pub renderer: TiledRenderBackend,
+    /// `renderer` is set explicitly with a layer property, so it takes
+    /// priority over a renderer registered for the layer class.
+    pub explicit_renderer: bool,
+    /// Z of the layer entity.
+    pub z: f32,
+    /// Distance by z to the next layer, y-sorted tiles are placed within it.
+    pub z_spacing: f32,
+    /// Always finite and positive, invalid values are replaced in
+    /// [TiledToolkitSettings::layer_settings].
+    pub pixels_per_unit: f32,
+    pub spawn_colliders: bool,
+    pub y_sort: bool,
+    /// Spawn sprites or tilemap for this layer, always `false` without
+    /// `render` feature.
+    pub spawn_sprites: bool,
+    /// Offset in pixels from the map anchor.
+    pub anchor_offset: Vec2,
+}
+
+impl TiledLayerSettings {
+    /// Returns `Transform` of the layer entity, which converts Tiled pixels
+    /// of the layer into Bevy units of the map. `offset` is the layer offset
+    /// in pixels, Y-up.
+    pub fn layer_transform(&self, offset: Vec2) -> Transform {
+        let scale = 1. / self.pixels_per_unit;
+        let offset = offset + self.anchor_offset;
+        Transform::from_translation((offset * scale).extend(self.z))
+            .with_scale(Vec3::new(scale, scale, 1.))
+    }
+
+    /// Returns local z of a tile or object inside the layer. If y-sorting is
+    /// enabled, lower sprites get bigger z, but it always stays below the
+    /// next layer.
+    pub fn sprite_z(&self, y: f32, map_height: f32) -> f32 {
+        if self.y_sort && map_height > 0. {
+            (1. - y / map_height).clamp(0., 1.) * self.z_spacing * 0.5
+        } else {
+            0.
+        }
+    }
+}
+
+// ───── Utility functions ────────────────────────────────────────────────── //
+
+fn is_valid_pixels_per_unit(value: f32) -> bool {
+    value.is_finite() && value > 0.
+}
+
+fn get_bool(properties: &tiled::Properties, name: &str) -> Option<bool> {
+    match properties.get(name) {
+        Some(tiled::PropertyValue::BoolValue(v)) => Some(*v),
+        _ => None,
+    }
+}
+
+fn get_f32(properties: &tiled::Properties, name: &str) -> Option<f32> {
+    match properties.get(name) {
+        Some(tiled::PropertyValue::FloatValue(v)) => Some(*v),
+        Some(tiled::PropertyValue::IntValue(v)) => Some(*v as f32),
+        _ => None,
+    }
+}
+
+fn get_string<'a>(
+    properties: &'a tiled::Properties,
+    name: &str,
+) -> Option<&'a str> {
+    match properties.get(name) {
+        Some(tiled::PropertyValue::StringValue(v)) => Some(v.as_str()),
+        _ => None,
+    }
+}
+
+// ───── Unit tests ───────────────────────────────────────────────────────── //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset_loader::TiledMapAsset;
+
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" renderorder="right-down"
+     width="1" height="1" tilewidth="16" tileheight="16" infinite="0">
+ <layer id="1" name="Valid" width="1" height="1">
+  <properties>
+   <property name="pixels_per_unit" type="float" value="16"/>
+   <property name="renderer" value="mesh"/>
+  </properties>
+  <data encoding="csv">0</data>
+ </layer>
+ <layer id="2" name="Invalid" width="1" height="1">
+  <properties>
+   <property name="pixels_per_unit" type="float" value="0"/>
+   <property name="renderer" type="int" value="1"/>
+  </properties>
+  <data encoding="csv">0</data>
+ </layer>
+</map>"#;
+
+    #[test]
+    fn test_layer_settings() {
+        let map_asset = TiledMapAsset::from_tmx(MAP);
+        let settings = TiledToolkitSettings {
+            pixels_per_unit: 8.,
+            ..default()
+        };
+        let context = TiledErrorContext::default();
+
+        let layer = map_asset.map.get_layer(0).unwrap();
+        let (layer_settings, errors) =
+            settings.layer_settings(&layer, 0, &context);
+        assert!(errors.is_empty());
+        assert_eq!(layer_settings.pixels_per_unit, 16.);
+        assert_eq!(
+            layer_settings.renderer,
+            TiledRenderBackend::Custom("mesh".to_string())
+        );
+        assert!(layer_settings.explicit_renderer);
+
+        let layer = map_asset.map.get_layer(1).unwrap();
+        let (layer_settings, errors) =
+            settings.layer_settings(&layer, 1, &context);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], TiledMapError::UnknownRenderer { .. }));
+        assert!(matches!(
+            errors[1],
+            TiledMapError::InvalidPixelsPerUnit { value, .. } if value == 0.
+        ));
+        assert_eq!(layer_settings.pixels_per_unit, 8.);
+        assert_eq!(layer_settings.renderer, TiledRenderBackend::Sprites);
+        assert!(!layer_settings.explicit_renderer);
+        let transform = layer_settings.layer_transform(Vec2::ZERO);
+        assert!(transform.scale.is_finite());
+    }
+
+    #[test]
+    fn test_invalid_global_pixels_per_unit() {
+        let map_asset = TiledMapAsset::from_tmx(MAP);
+        let settings = TiledToolkitSettings {
+            pixels_per_unit: -1.,
+            ..default()
+        };
+        let layer = map_asset.map.get_layer(1).unwrap();
+        let (layer_settings, errors) =
+            settings.layer_settings(&layer, 1, &TiledErrorContext::default());
+        // Both global setting and layer property are invalid.
+        assert_eq!(errors.len(), 3);
+        assert_eq!(layer_settings.pixels_per_unit, 1.);
+    }
+}